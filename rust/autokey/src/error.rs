@@ -1,11 +1,16 @@
 use std::ffi::OsString;
 use std::fmt::{self, Display};
 use std::io;
+use std::path::{Path, PathBuf};
 
 pub enum Error {
     Io(io::Error),
     Utf8(OsString),
     Empty,
+    EventName(String),
+    DayName(PathBuf, u8),
+    NoSolutions(PathBuf),
+    Source(PathBuf, syn::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -22,6 +27,36 @@ impl Display for Error {
                 name.to_string_lossy(),
             ),
             Empty => f.write_str("no source files found"),
+            EventName(name) => write!(
+                f,
+                "unexpected event folder name `{}`; expected `aocYYYY`, where YYYY is a known \
+                 Advent of Code event",
+                name,
+            ),
+            DayName(path, days) => write!(
+                f,
+                "{}: unexpected solution file name; expected `dayNN.rs`, where NN is between 01 \
+                 and {}",
+                relative(path).display(),
+                days,
+            ),
+            NoSolutions(path) => write!(
+                f,
+                "{}: no solutions found; expected a `pub fn part1` and/or `pub fn part2`",
+                relative(path).display(),
+            ),
+            Source(path, err) => {
+                // Columns are counted from 0, but editors count them from 1.
+                let start = err.span().start();
+                write!(
+                    f,
+                    "{}:{}:{}: {}",
+                    relative(path).display(),
+                    start.line,
+                    start.column + 1,
+                    err,
+                )
+            }
         }
     }
 }
@@ -31,3 +66,10 @@ impl From<io::Error> for Error {
         Error::Io(err)
     }
 }
+
+/// Makes `path` relative to the crate being compiled, if possible.
+pub(crate) fn relative(path: &Path) -> &Path {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .and_then(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path)
+}
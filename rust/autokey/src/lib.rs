@@ -3,6 +3,7 @@
 //!
 //! It assumes that the solutions crate depends on `anyhow`, as well as the following file layout:
 //!
+//! ```text
 //! lib.rs
 //! aoc2015/
 //!   day01.rs
@@ -12,13 +13,32 @@
 //!   ...
 //! aocXXXX/
 //!   ...
+//! ```
 //!
-//! Then, put `autokey::events!("src");` in lib.rs, and the aforementioned `mod`s and helper
-//! functions will be generated in it.
+//! Then, put `autokey::events!("src");` in lib.rs, and the aforementioned `mod`s and a
+//! `get_solution` function will be generated in it.
 //!
-//! It is assumed solution files contain two functions with these names and signatures:
+//! Any other module in an event folder, like `aoc2016/assembunny.rs` or `aoc2019/intcode/mod.rs`,
+//! is included as a module of the event too, for code shared between its days (which can get at it
+//! via `super::assembunny`). Shared modules aren't solution files, so they're not searched for
+//! `part1`/`part2`. Files named like `dayXX` still have to be valid solution files though.
 //!
-//! ```
+//! If the solutions crate declares a feature of the same name as an event folder (e.g. `aoc2015`)
+//! in its Cargo.toml, that event is only compiled while the feature is enabled. Events without a
+//! feature are always compiled, so a new event folder works without touching any manifest.
+//!
+//! Similarly, the solutions crate needs to declare an `embed-inputs` feature. If enabled, the
+//! puzzle inputs that exist at build time are embedded into the binary, instead of being read from
+//! the data folder at runtime.
+//!
+//! Since cargo doesn't know that the macro reads the source folder, the solutions crate should have
+//! a build script that tells it to rerun when anything in there changes (and in the data folder, if
+//! inputs are embedded). Otherwise, new solution files don't get picked up until lib.rs changes.
+//!
+//! Solution files may contain two functions with these names and signatures, which are registered
+//! for the event and day given by the file's location:
+//!
+//! ```ignore
 //! pub fn part1(_input: &[&str]) -> anyhow::Result<String> {
 //!     Err(anyhow::anyhow!("unimplemented"))
 //! }
@@ -30,19 +50,71 @@
 //!
 //! These correspond to the two parts of an Advent of Code task.
 //!
+//! Alternatively, a solution file can parse the input once for both parts, by defining a `parse`
+//! function and having the parts take a reference to its output instead:
+//!
+//! ```ignore
+//! pub fn parse(input: &[&str]) -> anyhow::Result<Vec<Instruction>> {
+//!     ...
+//! }
+//!
+//! pub fn part1(instructions: &[Instruction]) -> anyhow::Result<String> {
+//!     ...
+//! }
+//! ```
+//!
+//! The output of `parse` can't borrow from the input, since it gets passed around as a
+//! [`std::any::Any`].
+//!
+//! Alternative implementations of the same part can be kept around by naming them `part1_<name>`
+//! or `part2_<name>`; these get registered as named variants of the same key. The runner can then
+//! run all variants of a key side by side.
+//!
+//! Any other function with that signature, in any module of the crate, can be registered with the
+//! [`macro@aoc`] attribute instead, which takes the event, day and part it solves:
+//!
+//! ```ignore
+//! #[autokey::aoc(2021, 5, 1)]
+//! pub fn count_overlaps(input: &[&str]) -> anyhow::Result<String> {
+//!     ...
+//! }
+//! ```
+//!
+//! Giving the attribute a fourth argument registers the function as a named variant instead, e.g.
+//! `#[autokey::aoc(2021, 5, 1, bruteforce)]`.
+//!
+//! Each key can only have one solution registered for it, plus any number of differently named
+//! variants.
+//!
+//! Solutions can be tagged, e.g. as slow or only working due to properties of the specific input,
+//! with the [`macro@tags`] attribute; or all solutions in a module at once with [`day_tags!`]:
+//!
+//! ```ignore
+//! autokey::day_tags!("input-specific");
+//!
+//! #[autokey::tags("slow", "brute-force")]
+//! pub fn part2(input: &[&str]) -> anyhow::Result<String> {
+//!     ...
+//! }
+//! ```
+//!
 //! Majority of the code is stolen straight from https://github.com/dtolnay/automod, and from there
 //! kludged into place by trial-and-error.
 
 mod error;
 
-use crate::error::{Error, Result};
-use proc_macro::{Span, TokenStream};
+use crate::error::{relative, Error, Result};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use syn::parse::{Parse, ParseStream};
-use syn::{parse_macro_input, LitStr};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, LitInt, LitStr, Token};
 
-/// Arguments for [`events`] and [`days`].
+/// Arguments for [`events`].
 struct Arg {
     path: LitStr,
 }
@@ -55,107 +127,950 @@ impl Parse for Arg {
     }
 }
 
+/// Arguments for [`macro@tags`] and [`day_tags!`]: a comma-separated list of string literals.
+struct TagArgs {
+    tags: Vec<String>,
+}
+
+impl Parse for TagArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut tags = Vec::new();
+
+        for tag in Punctuated::<LitStr, Token![,]>::parse_terminated(input)? {
+            let value = tag.value();
+            if value.is_empty() || value.contains(char::is_whitespace) {
+                return Err(syn::Error::new(tag.span(), "tags must be non-empty and without spaces"));
+            }
+            tags.push(value);
+        }
+
+        Ok(TagArgs { tags })
+    }
+}
+
+/// Arguments for [`macro@aoc`]. Contains the event, day and part of the key, in that order,
+/// optionally followed by the name of the variant.
+struct KeyArgs {
+    key: (u16, u8, u8),
+    variant: Option<String>,
+}
+
+impl Parse for KeyArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let event: LitInt = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let day: LitInt = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let part: LitInt = input.parse()?;
+        let variant = if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: syn::Ident = input.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+            Some(name.to_string())
+        } else {
+            None
+        };
+
+        let key = (
+            event.base10_parse()?,
+            day.base10_parse()?,
+            part.base10_parse()?,
+        );
+
+        let known_event = match keys::Event::try_from(key.0) {
+            Ok(known_event) => known_event,
+            Err(_) => return Err(syn::Error::new(event.span(), "not a known Advent of Code event")),
+        };
+        let known_day = match keys::Day::try_from(key.1) {
+            Ok(known_day) if key.1 <= known_event.day_count() => known_day,
+            _ => {
+                let message = format!("day must be between 1 and {}", known_event.day_count());
+                return Err(syn::Error::new(day.span(), message));
+            }
+        };
+        if keys::Part::try_from(key.2).is_err() {
+            return Err(syn::Error::new(part.span(), "part must be either 1 or 2"));
+        }
+        if key.2 > known_event.part_count(known_day) {
+            return Err(syn::Error::new(part.span(), "the last day of an event only has part 1"));
+        }
+
+        Ok(KeyArgs { key, variant })
+    }
+}
+
+/// A function registered as a solution.
+struct Registration {
+    /// Path to the function.
+    function: TokenStream2,
+    /// Path to the `parse` function next to it, if it takes parsed input instead of the raw lines.
+    parse: Option<TokenStream2>,
+    /// The feature of the event folder it's in, if it's in one that's gated behind a feature.
+    feature: Option<String>,
+    /// Path of the file it's in, relative to the crate root.
+    source: String,
+    /// The first paragraph of its doc comment.
+    description: String,
+    /// Its own tags, as well as those of the module it's in.
+    tags: Vec<String>,
+}
+
+/// The modules in an event folder.
+#[derive(Default)]
+struct EventModules {
+    /// Solution files, along with the day they're for.
+    days: Vec<(String, u8)>,
+    /// Everything else.
+    shared: Vec<String>,
+}
+
+/// All registered solutions, by key and variant name. The main solution of a key has no name.
+type Registrations = BTreeMap<(u16, u8, u8), BTreeMap<Option<String>, Registration>>;
+
 /// Automatically includes all AoC solution files with the appropriate 'mod' directives, and
-/// generates a 'get_solutions'
+/// generates a 'get_solution' function indexing all registered solutions.
 #[proc_macro]
 pub fn events(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as Arg);
     let rel_path = input.path.value();
 
-    let dir = match std::env::var_os("CARGO_MANIFEST_DIR") {
-        Some(manifest_dir) => PathBuf::from(manifest_dir).join(rel_path),
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    let dir = match &manifest_dir {
+        Some(manifest_dir) => manifest_dir.join(rel_path),
         None => PathBuf::from(rel_path),
     };
 
-    let rel_path = input.path.value();
+    let features = match &manifest_dir {
+        Some(manifest_dir) => std::fs::read_to_string(manifest_dir.join("Cargo.toml"))
+            .map(|manifest| manifest_features(&manifest))
+            .map_err(Error::from),
+        None => Ok(Vec::new()),
+    };
 
-    let expanded = match event_folder_names(dir) {
-        Ok(names) => names
-            .clone()
-            .into_iter()
-            .map(|name| event_item(&name, &rel_path))
-            .chain(std::iter::once(event_indexer(names)))
-            .collect::<TokenStream>(),
+    match features.and_then(|features| expand_events(&dir, &features)) {
+        Ok(expanded) => expanded.into(),
         Err(err) => syn::Error::new(input.path.span(), err)
             .into_compile_error()
             .into(),
-    };
+    }
+}
 
-    TokenStream::from(expanded)
+/// Registers a function as the solution for a key, given as `#[aoc(event, day, part)]`, or as a
+/// named variant of it, given as `#[aoc(event, day, part, name)]`. The function needs to have the
+/// same signature as `part1`/`part2` in a solution file, and has to be visible from the crate root.
+///
+/// The function itself is left untouched; the registration is picked up by [`events`], which reads
+/// through the entire module tree of the crate.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    parse_macro_input!(args as KeyArgs);
+    let function = parse_macro_input!(item as syn::ItemFn);
+    let mut expanded = quote! { #function };
+
+    if let Some(err) = check_signature(&function) {
+        expanded.extend(err.into_compile_error());
+    }
+
+    expanded.into()
+}
+
+/// Tags the solution it's put on, given as `#[tags("tag", ...)]`. Tags end up in the generated
+/// `SOLUTIONS` table, where they can be used to filter solutions by.
+///
+/// Like [`macro@aoc`], this leaves the function untouched, and is picked up by [`events`].
+#[proc_macro_attribute]
+pub fn tags(args: TokenStream, item: TokenStream) -> TokenStream {
+    parse_macro_input!(args as TagArgs);
+    item
 }
 
-/// Automatically includes all dayXX.rs files in the corresponding folder. Used from within [`events`].
-/// TODO: Make it not pub and not a proc_macro, but an internal expansion function.
+/// Tags all solutions in the module it's put in, given as `day_tags!("tag", ...)`. See
+/// [`macro@tags`].
 #[proc_macro]
-pub fn days(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as Arg);
-    let rel_path = input.path.value();
+pub fn day_tags(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as TagArgs);
+    TokenStream::new()
+}
 
-    let dir = match std::env::var_os("CARGO_MANIFEST_DIR") {
-        Some(manifest_dir) => PathBuf::from(manifest_dir).join(input.path.value()),
-        None => PathBuf::from(input.path.value()),
-    };
+/// Generates the modules for all events found in `dir`, as well as the indexer for all solutions
+/// registered within them and the rest of the module tree. Events with a feature of the same name
+/// in `features` (the ones the crate declares) are gated behind it.
+fn expand_events(dir: &Path, features: &[String]) -> Result<TokenStream2> {
+    let mut registrations = BTreeMap::new();
+    let mut expanded = TokenStream2::new();
+    let events = event_folder_names(dir)?;
 
-    let folder = rel_path.split('/').last().unwrap();
+    for event in &events {
+        let event_number = event_from_name(event).ok_or_else(|| Error::EventName(event.clone()))?;
+        let known_event = keys::Event::try_from(event_number).unwrap();
+        let EventModules { days, shared } = event_modules(&dir.join(event), known_event)?;
+        expanded.extend(event_item(event, features.contains(event), &days, &shared));
 
-    let expanded = match source_file_names(dir) {
-        Ok(names) => names
-            .clone()
-            .into_iter()
-            .map(|name| day_item(&name))
-            .chain(std::iter::once(day_indexer(names, folder)))
-            .collect::<TokenStream>(),
-        Err(err) => syn::Error::new(input.path.span(), err)
-            .into_compile_error()
-            .into(),
-    };
+        // Shared modules may still contain solutions registered with `#[aoc]`.
+        for module in shared {
+            let source = module_file(&dir.join(event), &module);
+            let file = read_source_file(&source)?;
+            let sub_dir = dir.join(event).join(&module);
+            collect_registrations(
+                &file.items,
+                &[event.clone(), module],
+                &source,
+                &sub_dir,
+                &mut registrations,
+            )?;
+        }
+
+        for (day, day_number) in days {
+            let source = module_file(&dir.join(event), &day);
+
+            let module = vec![event.clone(), day.clone()];
+            let file = read_source_file(&source)?;
+            let registered = count_registrations(&registrations);
+
+            check_solution_items(&file.items, &source)?;
+            collect_default_parts(
+                &file.items,
+                &module,
+                (event_number, day_number),
+                &source,
+                &mut registrations,
+            )?;
+            collect_registrations(
+                &file.items,
+                &module,
+                &source,
+                &dir.join(event).join(&day),
+                &mut registrations,
+            )?;
+
+            if count_registrations(&registrations) == registered {
+                return Err(Error::NoSolutions(source));
+            }
+        }
+    }
+
+    let root = ["lib.rs", "main.rs"]
+        .into_iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file());
 
-    TokenStream::from(expanded)
+    if let Some(root) = root {
+        let file = read_source_file(&root)?;
+        collect_registrations(&file.items, &[], &root, dir, &mut registrations)?;
+    }
+
+    // Solutions outside of gated events don't depend on any feature.
+    for registration in registrations.values_mut().flat_map(BTreeMap::values_mut) {
+        registration.feature = registration.feature.take().filter(|f| features.contains(f));
+    }
+
+    expanded.extend(solution_indexer(registrations, &events, features));
+    Ok(expanded)
 }
 
-/// Generates a 'mod' entry for events with an existing 'aocXXXX' folder. The mod contains the
-/// results of an expansion of [`days`].
-fn event_item(name: &str, path: &str) -> TokenStream {
-    let ident = syn::Ident::new(name.split('.').next().unwrap(), Span::call_site().into());
-    let path = format!("{}/aoc{}", path, &name[3..]);
+/// The names of the features declared in the `[features]` table of a Cargo.toml. Only understands
+/// the usual layout of one `name = [...]` per line, which is all the solutions crate needs.
+fn manifest_features(manifest: &str) -> Vec<String> {
+    let mut features = Vec::new();
+    let mut in_features = false;
 
-    (quote::quote! {
-        mod #ident { autokey::days!(#path); }
-    })
-    .into()
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_features = line == "[features]";
+        } else if in_features && !line.starts_with('#') {
+            if let Some((name, _)) = line.split_once('=') {
+                features.push(name.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+
+    features
+}
+
+/// Generates a 'mod' entry for an existing 'aocXXXX' folder, containing a 'pub mod' entry for each
+/// of the solution files and shared modules in it. If `gated`, the module is only compiled if the
+/// feature of the same name is enabled.
+fn event_item(event: &str, gated: bool, days: &[(String, u8)], shared: &[String]) -> TokenStream2 {
+    let ident = format_ident!("{}", event);
+    let days = days.iter().map(|(day, _)| format_ident!("{}", day));
+    let shared = shared.iter().map(|module| format_ident!("{}", module));
+    let cfg = if gated { quote! { #[cfg(feature = #event)] } } else { TokenStream2::new() };
+
+    quote! {
+        #cfg
+        mod #ident {
+            #(pub mod #days;)*
+            #(pub mod #shared;)*
+        }
+    }
+}
+
+/// Generates a `#[cfg]` attribute that requires all of the given features to be enabled. Generates
+/// nothing if there are none.
+fn feature_cfg<'a>(features: impl IntoIterator<Item = &'a Option<String>>) -> TokenStream2 {
+    let mut features: Vec<_> = features.into_iter().flatten().collect();
+    features.sort();
+    features.dedup();
+
+    if features.is_empty() {
+        TokenStream2::new()
+    } else {
+        quote! { #[cfg(all(#(feature = #features),*))] }
+    }
 }
 
 /// Generates:
-/// ```
-/// pub fn get_solution(key: keys::Key) -> Option<(fn(&[&str]) -> anyhow::Result<String>, &'static str)> {
-///     match key.event {
-///         keys::Event::AoC2015 => aoc2015::get_solution(key.day, key),
+/// ```ignore
+/// pub fn get_solution(key: keys::Key) -> Option<(fn(&[&str]) -> anyhow::Result<String>, Input)> {
+///     match (key.event.year(), key.day, key.part) {
+///         (2015, keys::Day::Day01, keys::Part::One) => Some((crate::aoc2015::day01::part1, Input::Path("path-to-data-file"))),
 ///         ...,
 ///         _ => None,
 ///     }
 /// }
+///
+/// pub fn get_variants(key: keys::Key) -> &'static [(&'static str, fn(&[&str]) -> anyhow::Result<String>)] {
+///     match (key.event.year(), key.day, key.part) {
+///         (2021, keys::Day::Day08, keys::Part::Two) => &[("bruteforce", crate::aoc2021::day08::part2_bruteforce)],
+///         ...,
+///         _ => &[],
+///     }
+/// }
 /// ```
-/// for all events that have an "aocXXXX" folder.
-fn event_indexer(names: Vec<String>) -> TokenStream {
-    let items: Vec<_> = names
-        .into_iter()
-        .map(|name| {
-            let pat = quote::format_ident!("AoC{}", &name[3..]);
-            let fn_ident = quote::format_ident!("aoc{}", &name[3..]);
-            let call = quote::quote! { #fn_ident::get_solution(key.day, key.part) };
+/// for all registered solutions, as well as `get_parser` and `get_parsed_solution`, which give
+/// access to the separate steps of solutions that take parsed input. `get_parser` also gives the
+/// path of the `parse` function, since the two parts of a day may use different ones. Solutions
+/// that take parsed input are wrapped in glue functions that run the whole thing, so that `get_solution` and
+/// `get_variants` can treat them like any other.
+///
+/// Lastly, `is_compiled` tells whether the feature for an event in `events` is enabled (always
+/// true for events without one in `features`), `EVENTS` lists those events, and `SOLUTIONS` lists
+/// metadata for all registered solutions, sorted by key.
+fn solution_indexer(
+    registrations: Registrations,
+    events: &[String],
+    features: &[String],
+) -> TokenStream2 {
+    let working_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap();
 
-            quote::quote! { keys::Event::#pat => #call }
-        })
-        .collect();
+    let mut solutions = Vec::new();
+    let mut variants = Vec::new();
+    let mut parsers = BTreeMap::new();
+    let mut parser_arms = Vec::new();
+    let mut parsed_solutions = Vec::new();
+    let mut glue = Vec::new();
+    let mut infos = Vec::new();
+
+    for ((event, day, part), functions) in registrations {
+        let data_file = Path::new(&working_dir).parent().unwrap()
+            .join("data")
+            .join(format!("aoc{}", event))
+            .join(format!("day{:02}.txt", day));
+
+        // Inputs can only be embedded if they exist. Otherwise, fall back to reading the file at
+        // runtime, which at least gives a helpful error message if it's still missing by then.
+        let input = if data_file.is_file() {
+            let data_file = format!("{}", data_file.display());
+            quote! { input!(#data_file) }
+        } else {
+            let data_file = format!("{}", data_file.display());
+            quote! { Input::Path(#data_file) }
+        };
+        let data_path = format!("{}", data_file.display());
 
-    (quote::quote! {
-        pub fn get_solution(key: keys::Key) -> Option<(fn(&[&str]) -> anyhow::Result<String>, &'static str)> {
-            match key.event {
-                #(#items),*,
+        let glue_name = format!("aoc{}_day{:02}_part{}", event, day, part);
+        let day_ident = format_ident!("Day{:02}", day);
+        let part_ident = format_ident!("{}", if part == 1 { "One" } else { "Two" });
+        let pattern = quote! { (#event, keys::Day::#day_ident, keys::Part::#part_ident) };
+
+        let variant_cfg = feature_cfg(functions.iter().filter(|(variant, _)| variant.is_some()).map(|(_, r)| &r.feature));
+
+        let mut named = Vec::new();
+        if let Some(main) = functions.get(&None) {
+            let Registration { source, description, feature, tags, .. } = main;
+            let compiled = match feature {
+                Some(feature) => quote! { cfg!(feature = #feature) },
+                None => quote! { true },
+            };
+
+            infos.push(quote! {
+                SolutionInfo {
+                    key: keys::Key {
+                        event: keys::Event::new(#event),
+                        day: keys::Day::#day_ident,
+                        part: keys::Part::#part_ident,
+                    },
+                    compiled: #compiled,
+                    source: #source,
+                    description: #description,
+                    data: #data_path,
+                    tags: &[#(#tags),*],
+                }
+            });
+        }
+
+        for (variant, Registration { function, parse, feature, .. }) in functions {
+            let cfg = feature_cfg([&feature]);
+            let function = match parse {
+                None => function,
+                Some(parse) => {
+                    let ident = match &variant {
+                        Some(name) => format_ident!("{}_{}", glue_name, name),
+                        None => format_ident!("{}", glue_name),
+                    };
+
+                    glue.push(quote! {
+                        #cfg
+                        pub fn #ident(input: &[&str]) -> anyhow::Result<String> {
+                            #function(&#parse(input)?)
+                        }
+                    });
+
+                    if variant.is_none() {
+                        let parsed_ident = format_ident!("{}_parsed", ident);
+                        glue.push(quote! {
+                            #cfg
+                            pub fn #parsed_ident(input: &dyn std::any::Any) -> anyhow::Result<String> {
+                                #function(downcast(#parse, input)?)
+                            }
+                        });
+                        parsed_solutions.push(quote! { #cfg #pattern => Some(parse_glue::#parsed_ident) });
+
+                        // Both parts usually share the same `parse`, but they don't have to; so
+                        // parsers are registered per part, and named by their path, which lets
+                        // callers tell whether two parts can share the parsed input.
+                        let path = parse.to_string().replace(' ', "");
+                        let path = path.trim_start_matches("crate::").to_string();
+                        let parse_ident = format_ident!("{}", path.replace("::", "_"));
+                        parser_arms.push(quote! { #cfg #pattern => Some((#path, parse_glue::#parse_ident)) });
+                        parsers.entry(path).or_insert((parse_ident, parse, cfg.clone()));
+                    }
+
+                    quote! { parse_glue::#ident }
+                }
+            };
+
+            match variant {
+                Some(name) => named.push(quote! { (#name, #function) }),
+                None => solutions.push(quote! { #cfg #pattern => Some((#function, #input)) }),
+            }
+        }
+
+        if !named.is_empty() {
+            variants.push(quote! { #variant_cfg #pattern => &[#(#named,)*] });
+        }
+    }
+
+    for (ident, parse, cfg) in parsers.into_values() {
+        glue.push(quote! {
+            #cfg
+            pub fn #ident(input: &[&str]) -> anyhow::Result<super::ParsedInput> {
+                Ok(Box::new(#parse(input)?))
+            }
+        });
+    }
+
+    let compiled = events.iter().filter(|event| features.contains(event)).map(|event| {
+        let year = event_from_name(event).unwrap();
+        quote! { #year => cfg!(feature = #event) }
+    });
+    let event_list = events.iter().map(|event| {
+        let year = event_from_name(event).unwrap();
+        quote! { keys::Event::new(#year) }
+    });
+
+    let glue = if glue.is_empty() {
+        TokenStream2::new()
+    } else {
+        quote! {
+            mod parse_glue {
+                #(#glue)*
+
+                /// Recovers the output of `parse` from the type-erased parsed input.
+                #[allow(dead_code)] // If all events with parsed solutions are disabled.
+                fn downcast<T: 'static>(
+                    _parse: fn(&[&str]) -> anyhow::Result<T>,
+                    input: &dyn std::any::Any,
+                ) -> anyhow::Result<&T> {
+                    input.downcast_ref().ok_or(anyhow::anyhow!("parsed input has an unexpected type"))
+                }
+            }
+        }
+    };
+
+    quote! {
+        #glue
+
+        /// The output of the `parse` function of a solution file, with its type erased.
+        pub type ParsedInput = Box<dyn std::any::Any>;
+
+        /// Metadata about a registered solution.
+        pub struct SolutionInfo {
+            /// The key the solution is registered for.
+            pub key: keys::Key,
+            /// Whether the solution is compiled in, i.e. whether its event's feature is enabled.
+            pub compiled: bool,
+            /// Path of the file the solution is in, relative to the crate root.
+            pub source: &'static str,
+            /// The first paragraph of the solution's doc comment, as a single line.
+            pub description: &'static str,
+            /// Path of the puzzle input for the solution.
+            pub data: &'static str,
+            /// The tags given to the solution, sorted alphabetically.
+            pub tags: &'static [&'static str],
+        }
+
+        /// All events that have a folder, whether they are compiled or not, sorted by year.
+        pub static EVENTS: &[keys::Event] = &[#(#event_list,)*];
+
+        /// All registered solutions, sorted by key. Variants are not included.
+        pub static SOLUTIONS: &[SolutionInfo] = &[#(#infos,)*];
+
+        /// Where to get the puzzle input for a solution from.
+        pub enum Input {
+            /// The path of the data file, to be read at runtime.
+            Path(&'static str),
+            /// The contents of the data file, embedded at build time.
+            Embedded(&'static str),
+        }
+
+        impl Input {
+            /// Gets the puzzle input, line by line. Embedded inputs don't touch the file system.
+            pub fn read(&self) -> std::io::Result<Vec<String>> {
+                match self {
+                    Input::Path(path) => std::fs::read_to_string(path)
+                        .map(|contents| contents.lines().map(String::from).collect()),
+                    Input::Embedded(contents) => Ok(contents.lines().map(String::from).collect()),
+                }
+            }
+        }
+
+        #[cfg(feature = "embed-inputs")]
+        macro_rules! input {
+            ($path:literal) => { Input::Embedded(include_str!($path)) };
+        }
+
+        #[cfg(not(feature = "embed-inputs"))]
+        macro_rules! input {
+            ($path:literal) => { Input::Path($path) };
+        }
+
+        pub fn get_solution(key: keys::Key) -> Option<(fn(&[&str]) -> anyhow::Result<String>, Input)> {
+            match (key.event.year(), key.day, key.part) {
+                #(#solutions,)*
+                _ => None,
+            }
+        }
+
+        pub fn get_variants(key: keys::Key) -> &'static [(&'static str, fn(&[&str]) -> anyhow::Result<String>)] {
+            match (key.event.year(), key.day, key.part) {
+                #(#variants,)*
+                _ => &[],
+            }
+        }
+
+        pub fn get_parser(key: keys::Key) -> Option<(&'static str, fn(&[&str]) -> anyhow::Result<ParsedInput>)> {
+            match (key.event.year(), key.day, key.part) {
+                #(#parser_arms,)*
                 _ => None,
             }
         }
-    }).into()
+
+        pub fn get_parsed_solution(key: keys::Key) -> Option<fn(&dyn std::any::Any) -> anyhow::Result<String>> {
+            match (key.event.year(), key.day, key.part) {
+                #(#parsed_solutions,)*
+                _ => None,
+            }
+        }
+
+        pub fn is_compiled(event: keys::Event) -> bool {
+            match event.year() {
+                #(#compiled,)*
+                _ => true,
+            }
+        }
+    }
+}
+
+/// Registers the `part1` and `part2` functions of a solution file under the key derived from the
+/// file's location, unless they carry an explicit [`macro@aoc`] attribute. Functions named
+/// `part1_<name>` and `part2_<name>` are registered as variants called `<name>`.
+fn collect_default_parts(
+    items: &[syn::Item],
+    module: &[String],
+    (event, day): (u16, u8),
+    source: &Path,
+    registrations: &mut Registrations,
+) -> Result<()> {
+    for item in items {
+        if let syn::Item::Fn(function) = item {
+            let (part, variant) = match solution_name(&function.sig.ident.to_string()) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            if !function.attrs.iter().any(|attr| is_attribute(attr, "aoc")) {
+                let known_event = keys::Event::try_from(event).unwrap();
+                let known_day = keys::Day::try_from(day).unwrap();
+                if part > known_event.part_count(known_day) {
+                    return Err(Error::Source(
+                        source.to_path_buf(),
+                        syn::Error::new(
+                            function.sig.ident.span(),
+                            "the last day of an event only has part 1",
+                        ),
+                    ));
+                }
+
+                let key = (event, day, part);
+                register(registrations, key, variant, module, items, source, function)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the top-level functions of a solution file for mistakes that would otherwise result in
+/// confusing errors pointing at the [`events`] invocation, or in silently missing solutions.
+fn check_solution_items(items: &[syn::Item], source: &Path) -> Result<()> {
+    for item in items {
+        let function = match item {
+            syn::Item::Fn(function) => function,
+            _ => continue,
+        };
+
+        let name = function.sig.ident.to_string();
+        let problem = if solution_name(&name).is_some() {
+            check_signature(function)
+        } else if ["part1", "part2", "partone", "parttwo"].contains(&&*name.to_lowercase().replace('_', "")) {
+            Some(syn::Error::new(
+                function.sig.ident.span(),
+                format!(
+                    "`{}` looks like a solution, but only `part1` and `part2` get registered",
+                    name,
+                ),
+            ))
+        } else {
+            None
+        };
+
+        if let Some(err) = problem {
+            return Err(Error::Source(source.to_path_buf(), err));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks whether the signature of a solution function can be registered, returning an error
+/// describing the problem if not.
+fn check_signature(function: &syn::ItemFn) -> Option<syn::Error> {
+    let name = &function.sig.ident;
+
+    if let syn::Visibility::Inherited = function.vis {
+        return Some(syn::Error::new(
+            name.span(),
+            format!(
+                "`{}` needs to be visible from the crate root to be registered as a solution; \
+                 mark it `pub` or `pub(crate)`",
+                name,
+            ),
+        ));
+    }
+
+    if function.sig.inputs.len() != 1 {
+        return Some(syn::Error::new(
+            name.span(),
+            format!(
+                "`{}` should take exactly one argument: the puzzle input as `&[&str]`, or a \
+                 reference to the output of `parse`",
+                name,
+            ),
+        ));
+    }
+
+    if let syn::ReturnType::Default = function.sig.output {
+        return Some(syn::Error::new(
+            name.span(),
+            format!("`{}` should return `anyhow::Result<String>`", name),
+        ));
+    }
+
+    None
+}
+
+/// Gets the part and variant name for a function name that gets registered as a solution by
+/// default, i.e. `part1`, `part2`, `part1_<name>` or `part2_<name>`.
+fn solution_name(name: &str) -> Option<(u8, Option<String>)> {
+    match name.split_once('_') {
+        Some(("part1", variant)) => Some((1, Some(variant.to_string()))),
+        Some(("part2", variant)) => Some((2, Some(variant.to_string()))),
+        None if name == "part1" => Some((1, None)),
+        None if name == "part2" => Some((2, None)),
+        _ => None,
+    }
+}
+
+/// The total amount of solutions registered so far, including variants.
+fn count_registrations(registrations: &Registrations) -> usize {
+    registrations.values().map(BTreeMap::len).sum()
+}
+
+/// Walks through the given module items, and registers all functions that have an [`macro@aoc`]
+/// attribute. `source` is the file the items are from, and `dir` is the folder in which files for
+/// submodules declared in `items` are located.
+fn collect_registrations(
+    items: &[syn::Item],
+    module: &[String],
+    source: &Path,
+    dir: &Path,
+    registrations: &mut Registrations,
+) -> Result<()> {
+    for item in items {
+        match item {
+            syn::Item::Fn(function) => {
+                for attr in function.attrs.iter().filter(|attr| is_attribute(attr, "aoc")) {
+                    // Malformed arguments are reported by the attribute itself, with a much more
+                    // useful span than we could provide here.
+                    if let Ok(KeyArgs { key, variant }) = in_fallback(|| attr.parse_args()) {
+                        register(registrations, key, variant, module, items, source, function)?;
+                    }
+                }
+            }
+            syn::Item::Mod(submodule) => {
+                let name = submodule.ident.to_string();
+                let sub_dir = dir.join(&name);
+                let mut path = module.to_vec();
+                path.push(name.clone());
+
+                match &submodule.content {
+                    Some((_, items)) => {
+                        collect_registrations(items, &path, source, &sub_dir, registrations)?
+                    }
+                    None => {
+                        let source = module_file(dir, &name);
+
+                        // A missing module file is reported by the compiler anyway.
+                        if source.is_file() {
+                            let file = read_source_file(&source)?;
+                            collect_registrations(&file.items, &path, &source, &sub_dir, registrations)?;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds `function` in `module` as the solution for `key`, or as the named `variant` of it. `items`
+/// are the contents of `module`, which are searched for a `parse` function if `function` doesn't
+/// take the raw input, and `source` is the file they are from. Fails if there already is a solution
+/// for the key and variant.
+fn register(
+    registrations: &mut Registrations,
+    key: (u16, u8, u8),
+    variant: Option<String>,
+    module: &[String],
+    items: &[syn::Item],
+    source: &Path,
+    function: &syn::ItemFn,
+) -> Result<()> {
+    let error = |message: String| Error::Source(
+        source.to_path_buf(),
+        syn::Error::new(function.sig.ident.span(), message),
+    );
+
+    let feature = module.first().filter(|event| event_from_name(event).is_some()).cloned();
+    let module: Vec<_> = module.iter().map(|m| format_ident!("{}", m)).collect();
+    let name = format_ident!("{}", function.sig.ident.to_string());
+
+    let parse = if takes_raw_input(function) {
+        None
+    } else if items.iter().any(|item| matches!(item, syn::Item::Fn(f) if f.sig.ident == "parse")) {
+        Some(quote! { crate #(::#module)*::parse })
+    } else {
+        return Err(error(format!(
+            "`{}` takes parsed input, but there is no `parse` function next to it",
+            name,
+        )));
+    };
+
+    let functions = registrations.entry(key).or_default();
+    if functions.contains_key(&variant) {
+        return Err(error(format!(
+            "more than one solution registered for {}-{:02}{}{}",
+            key.0,
+            key.1,
+            if key.2 == 1 { 'a' } else { 'b' },
+            match &variant {
+                Some(name) => format!(" (variant `{}`)", name),
+                None => String::new(),
+            },
+        )));
+    }
+
+    let registration = Registration {
+        function: quote! { crate #(::#module)*::#name },
+        parse,
+        feature,
+        source: relative(source).display().to_string(),
+        description: first_doc_line(&function.attrs),
+        tags: collect_tags(items, function),
+    };
+    functions.insert(variant, registration);
+
+    Ok(())
+}
+
+/// Gets the first paragraph of the doc comment in `attrs` as a single line, or an empty string if
+/// there is none.
+fn first_doc_line(attrs: &[syn::Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(doc), .. })) => {
+                Some(doc.value().trim().to_string())
+            }
+            _ => None,
+        })
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Checks whether a function takes the raw puzzle input, `&[&str]`, as opposed to the output of a
+/// `parse` function.
+fn takes_raw_input(function: &syn::ItemFn) -> bool {
+    let ty = match function.sig.inputs.first() {
+        Some(syn::FnArg::Typed(arg)) => &*arg.ty,
+        _ => return true,
+    };
+
+    if let syn::Type::Reference(slice) = ty {
+        if let syn::Type::Slice(slice) = &*slice.elem {
+            if let syn::Type::Reference(line) = &*slice.elem {
+                return matches!(&*line.elem, syn::Type::Path(path) if path.path.is_ident("str"));
+            }
+        }
+    }
+
+    false
+}
+
+/// Checks whether an attribute is the one from this crate called `name`, either imported or as
+/// `autokey::name`.
+fn is_attribute(attr: &syn::Attribute, name: &str) -> bool {
+    attr.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+/// Collects the tags given to a solution function with [`macro@tags`], as well as the ones given to
+/// all of `items` (the module it is in) with [`day_tags!`]. Malformed tags are skipped, since
+/// they're reported by the macros themselves.
+fn collect_tags(items: &[syn::Item], function: &syn::ItemFn) -> Vec<String> {
+    let day_tags = items.iter().filter_map(|item| match item {
+        syn::Item::Macro(item) if item.mac.path.segments.last()?.ident == "day_tags" => {
+            in_fallback(|| item.mac.parse_body::<TagArgs>()).ok()
+        }
+        _ => None,
+    });
+
+    let part_tags = function
+        .attrs
+        .iter()
+        .filter(|attr| is_attribute(attr, "tags"))
+        .filter_map(|attr| in_fallback(|| attr.parse_args::<TagArgs>()).ok());
+
+    let mut tags: Vec<_> = day_tags.chain(part_tags).flat_map(|args| args.tags).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Reads and parses a source file.
+///
+/// This uses proc-macro2's own implementation of spans instead of the compiler's, since the latter
+/// can't point into arbitrary files, and the former at least knows line and column numbers for
+/// error messages. Consequently, tokens from the parsed file must not end up in the output.
+fn read_source_file(path: &Path) -> Result<syn::File> {
+    let source = std::fs::read_to_string(path)?;
+
+    let file = in_fallback(|| syn::parse_file(&source));
+
+    file.map_err(|err| {
+        let message = format!("failed to parse file: {}", err);
+        Error::Source(path.to_path_buf(), syn::Error::new(err.span(), message))
+    })
+}
+
+/// Gets the file for the module `name` declared in `dir`: either "name.rs", or "name/mod.rs" if
+/// that's the one that exists.
+fn module_file(dir: &Path, name: &str) -> PathBuf {
+    let nested = dir.join(name).join("mod.rs");
+    if nested.is_file() {
+        nested
+    } else {
+        dir.join(format!("{}.rs", name))
+    }
+}
+
+/// Runs `f` with proc-macro2's own implementation of spans; see [`read_source_file`]. Parsing
+/// tokens from a file read that way needs to happen like this too, since it involves creating new
+/// spans, which can't be mixed with spans from the compiler.
+fn in_fallback<T>(f: impl FnOnce() -> T) -> T {
+    proc_macro2::fallback::force();
+    let result = f();
+    proc_macro2::fallback::unforce();
+    result
+}
+
+/// Gets the event from the name of an event folder, e.g. 2015 from "aoc2015".
+fn event_from_name(name: &str) -> Option<u16> {
+    let digits = name.strip_prefix("aoc")?;
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let event = digits.parse().ok()?;
+    keys::Event::try_from(event).ok()?;
+    Some(event)
+}
+
+/// Gets the day from the name of a solution file without extension, e.g. 3 from "day03".
+fn day_from_name(name: &str) -> Option<u8> {
+    let digits = name.strip_prefix("day")?;
+    if digits.len() != 2 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let day = digits.parse().ok()?;
+    keys::Day::try_from(day).ok()?;
+    Some(day)
+}
+
+/// Sorts the modules in an event folder into solution files, along with their day, and shared
+/// modules. Anything named like a solution file has to be a valid one, so that typos like "day1.rs"
+/// don't silently turn into shared modules.
+fn event_modules(dir: &Path, event: keys::Event) -> Result<EventModules> {
+    let mut modules = EventModules::default();
+
+    for name in module_names(dir)? {
+        if let Some(day) = day_from_name(&name).filter(|&day| day <= event.day_count()) {
+            modules.days.push((name, day));
+        } else if name.starts_with("day") {
+            return Err(Error::DayName(module_file(dir, &name), event.day_count()));
+        } else {
+            modules.shared.push(name);
+        }
+    }
+
+    Ok(modules)
 }
 
 /// Gets the names of all folders starting with "aoc" in the given folder.
@@ -192,64 +1107,25 @@ fn event_folder_names<P: AsRef<Path>>(dir: P) -> Result<Vec<String>> {
     Ok(names)
 }
 
-/// Generates a 'pub mod' entry for an existing dayxx.rs file.
-fn day_item(name: &str) -> TokenStream {
-    let ident = syn::Ident::new(name.split('.').next().unwrap(), Span::call_site().into());
-    (quote::quote! {
-        pub mod #ident;
-    })
-    .into()
-}
-
-/// Generates:
-/// ```
-/// pub fn get_solution(day: keys::Day, part: keys::Part) -> Option<(fn(&[&str]) -> anyhow::Result<String>, &'static str)> {
-///     match (day, part) {
-///         (keys::Day::Day01, keys::Part::One) => Some(day01::part1, "path-to-data-file"),
-///         ...,
-///         _ => None
-///     }
-/// }
-/// ```
-/// for solution files that exist.
-fn day_indexer(names: Vec<String>, folder: &str) -> TokenStream {
-    let part_one_ident = quote::format_ident!("One");
-    let part_two_ident = quote::format_ident!("Two");
-    let working_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap();
-
-    let items: Vec<_> = names.into_iter().map(|name| {
-        let data_file = Path::new(&working_dir).parent().unwrap()
-            .join("data")
-            .join(folder)
-            .join(&format!("day{:02}.txt", &name[3..]));
-
-        let data_file = format!("{}", data_file.display());
-
-        let pat_ident = quote::format_ident!("Day{:02}", &name[3..]);
-        let pat1 = quote::quote! { (keys::Day::#pat_ident, keys::Part::#part_one_ident) };
-        let pat2 = quote::quote! { (keys::Day::#pat_ident, keys::Part::#part_two_ident) };
-        let fn_ident = quote::format_ident!("day{:02}", &name[3..]);
-
-        quote::quote! { #pat1 => Some((#fn_ident::part1, #data_file)), #pat2 => Some((#fn_ident::part2, #data_file)) }
-    }).collect();
-
-    (quote::quote! {
-        pub fn get_solution(day: keys::Day, part: keys::Part) -> Option<(fn(&[&str]) -> anyhow::Result<String>, &'static str)> {
-            match (day, part) {
-                #(#items),*,
-                _=> None,
-            }
-        }
-    }).into()
-}
-
-/// Gets the names of all source files in a directory (excluding extension).
-fn source_file_names<P: AsRef<Path>>(dir: P) -> Result<Vec<String>> {
+/// Gets the names of all modules in a directory: source files (excluding extension), and folders
+/// with a "mod.rs" in them.
+fn module_names<P: AsRef<Path>>(dir: P) -> Result<Vec<String>> {
     let mut names = Vec::new();
     let mut failures = Vec::new();
 
     for entry in std::fs::read_dir(dir)? {
         let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            // Other folders just hold the submodules of a solution file, like "day18/".
+            if entry.path().join("mod.rs").is_file() {
+                match entry.file_name().into_string() {
+                    Ok(name) => names.push(name),
+                    Err(non_utf8) => failures.push(non_utf8),
+                }
+            }
+            continue;
+        }
+
         if !entry.file_type()?.is_file() {
             continue;
         }
@@ -285,3 +1161,46 @@ fn source_file_names<P: AsRef<Path>>(dir: P) -> Result<Vec<String>> {
     names.sort();
     Ok(names)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A solution file with both parts.
+    const SOLUTION: &str = "pub fn part1(_input: &[&str]) -> anyhow::Result<String> { todo!() }\n\
+                            pub fn part2(_input: &[&str]) -> anyhow::Result<String> { todo!() }\n";
+
+    /// Creates a source folder with day 1 of each of the given events, and returns its path.
+    fn source_folder(name: &str, events: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("autokey-{}-{}", name, std::process::id()));
+        for event in events {
+            std::fs::create_dir_all(dir.join(event)).unwrap();
+            std::fs::write(dir.join(event).join("day01.rs"), SOLUTION).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn features_from_manifest() {
+        let manifest = "[package]\nname = \"solutions\"\n\n\
+                        [features]\n# A comment = not a feature\ndefault = [\"aoc2015\"]\n\
+                        aoc2015 = []\n\"embed-inputs\" = []\n\n\
+                        [dependencies]\nanyhow = \"1.0\"\n";
+        assert_eq!(manifest_features(manifest), vec!["default", "aoc2015", "embed-inputs"]);
+        assert!(manifest_features("[package]\nname = \"solutions\"\n").is_empty());
+    }
+
+    #[test]
+    fn only_declared_events_are_gated() {
+        let dir = source_folder("gating", &["aoc2015", "aoc2022"]);
+        let expanded = expand_events(&dir, &["aoc2015".to_string()]).map_err(|e| e.to_string());
+        std::fs::remove_dir_all(&dir).unwrap();
+        let expanded = expanded.unwrap().to_string();
+
+        // Both events get a module, but only the one with a declared feature depends on it.
+        assert!(expanded.contains("mod aoc2015"));
+        assert!(expanded.contains("mod aoc2022"));
+        assert!(expanded.contains("feature = \"aoc2015\""));
+        assert!(!expanded.contains("feature = \"aoc2022\""));
+    }
+}
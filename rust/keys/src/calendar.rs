@@ -0,0 +1,166 @@
+//! When puzzles unlock: every one of them at midnight UTC-5, on its day in December.
+
+use crate::{Day, Event, Key, Part};
+use std::time::{Duration, SystemTime};
+
+/// Seconds in a day.
+const DAY: u64 = 24 * 60 * 60;
+
+/// Midnight in UTC-5, as seconds since midnight in UTC.
+const UNLOCK_OFFSET: u64 = 5 * 60 * 60;
+
+impl Event {
+    /// When the puzzle for `day` of the event unlocks.
+    pub fn unlock_time(self, day: Day) -> SystemTime {
+        let days = days_until_december(self.year() as u64) + day as u64 - 1;
+        SystemTime::UNIX_EPOCH + Duration::from_secs(days * DAY + UNLOCK_OFFSET)
+    }
+
+    /// The latest event that has started. Before the very first one did, that's the first one.
+    pub fn current() -> Event {
+        current_at(SystemTime::now())
+    }
+}
+
+impl Key {
+    /// When the puzzle for this key unlocks. Both parts of a day unlock at the same time, though
+    /// part 2 is only visible once part 1 has been solved.
+    pub fn unlock_time(&self) -> SystemTime {
+        self.event.unlock_time(self.day)
+    }
+}
+
+/// The keys of the puzzles that unlocked most recently, while an event is running; that is, until a
+/// day after its last puzzle unlocked. Empty for the rest of the year.
+pub fn today() -> Vec<Key> {
+    today_at(SystemTime::now())
+}
+
+/// The event and day of the next puzzle to unlock.
+pub fn next_unlock() -> (Event, Day) {
+    next_unlock_at(SystemTime::now())
+}
+
+/// The keys of the puzzles that unlocked most recently at the given time; see [`today`].
+fn today_at(now: SystemTime) -> Vec<Key> {
+    let event = current_at(now);
+    let last_day = event.days().last().unwrap();
+
+    if now < event.unlock_time(Day::Day01)
+        || now >= event.unlock_time(last_day) + Duration::from_secs(DAY)
+    {
+        return vec![];
+    }
+
+    let Some(day) = event.days().filter(|&day| event.unlock_time(day) <= now).last() else {
+        return vec![];
+    };
+    (1..=event.part_count(day))
+        .map(|part| Key { event, day, part: Part::try_from(part).unwrap() })
+        .collect()
+}
+
+/// The event and day of the next puzzle to unlock at the given time; see [`next_unlock`].
+fn next_unlock_at(now: SystemTime) -> (Event, Day) {
+    let event = current_at(now);
+
+    match event.days().find(|&day| event.unlock_time(day) > now) {
+        Some(day) => (event, day),
+        None => (Event::new(event.year() + 1), Day::Day01),
+    }
+}
+
+/// The latest event that has started at the given time; see [`Event::current`].
+fn current_at(now: SystemTime) -> Event {
+    let mut event = Event::new(Event::FIRST_YEAR);
+    loop {
+        let next = Event::new(event.year() + 1);
+        if next.unlock_time(Day::Day01) > now {
+            return event;
+        }
+        event = next;
+    }
+}
+
+/// The number of days from the Unix epoch to the first of December of `year`.
+fn days_until_december(year: u64) -> u64 {
+    // The number of leap days from year 0 up to and including `year`.
+    let leap_days = |year: u64| year / 4 - year / 100 + year / 400;
+
+    // January through November have 334 days, plus the leap day of `year` if there is one.
+    365 * (year - 1970) + leap_days(year) - leap_days(1969) + 334
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The given number of seconds after the Unix epoch.
+    fn at(seconds: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    /// The keys of both parts (or the only part) of the given day.
+    fn keys(year: u16, day: Day) -> Vec<Key> {
+        let event = Event::new(year);
+        [Part::One, Part::Two].into_iter().map(|part| Key { event, day, part }).filter(Key::exists).collect()
+    }
+
+    /// 2015-12-01 05:00:00 UTC, which is midnight in UTC-5.
+    const FIRST_UNLOCK: u64 = 1448946000;
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(Event::new(2015).unlock_time(Day::Day01), at(FIRST_UNLOCK));
+        assert_eq!(Event::new(2015).unlock_time(Day::Day25), at(FIRST_UNLOCK + 24 * DAY));
+        // Both 2016 and 2024 are leap years.
+        assert_eq!(Event::new(2016).unlock_time(Day::Day01), at(1480568400));
+        assert_eq!(Event::new(2024).unlock_time(Day::Day01), at(1733029200));
+        assert_eq!(Event::new(2025).unlock_time(Day::Day01), at(1764565200));
+    }
+
+    #[test]
+    fn around_first_unlock() {
+        let before = at(FIRST_UNLOCK - 1);
+        assert_eq!(today_at(before), vec![]);
+        assert_eq!(next_unlock_at(before), (Event::new(2015), Day::Day01));
+        assert_eq!(current_at(before), Event::new(2015));
+
+        let after = at(FIRST_UNLOCK);
+        assert_eq!(today_at(after), keys(2015, Day::Day01));
+        assert_eq!(next_unlock_at(after), (Event::new(2015), Day::Day02));
+    }
+
+    #[test]
+    fn before_first_event() {
+        let now = at(1275350400); // 2010-06-01
+        assert_eq!(current_at(now), Event::new(2015));
+        assert_eq!(today_at(now), vec![]);
+        assert_eq!(next_unlock_at(now), (Event::new(2015), Day::Day01));
+    }
+
+    #[test]
+    fn after_last_day() {
+        // Day 25 is still today until the next midnight in UTC-5, and has just one part.
+        let last_unlock = FIRST_UNLOCK + 24 * DAY;
+        assert_eq!(today_at(at(last_unlock)), keys(2015, Day::Day25));
+        assert_eq!(today_at(at(last_unlock + DAY - 1)), keys(2015, Day::Day25));
+        assert_eq!(today_at(at(last_unlock)).len(), 1);
+
+        assert_eq!(today_at(at(last_unlock + DAY)), vec![]);
+        assert_eq!(next_unlock_at(at(last_unlock)), (Event::new(2016), Day::Day01));
+        assert_eq!(current_at(at(last_unlock + DAY)), Event::new(2015));
+    }
+
+    #[test]
+    fn twelve_days_from_2025() {
+        let first_unlock = 1764565200;
+        let last_unlock = first_unlock + 11 * DAY;
+        assert_eq!(today_at(at(last_unlock - 1)), keys(2025, Day::Day11));
+        assert_eq!(today_at(at(last_unlock)), keys(2025, Day::Day12));
+        assert_eq!(today_at(at(last_unlock)).len(), 1);
+        assert_eq!(today_at(at(last_unlock + DAY)), vec![]);
+        assert_eq!(next_unlock_at(at(last_unlock)), (Event::new(2026), Day::Day01));
+        assert_eq!(next_unlock_at(at(last_unlock - 1)), (Event::new(2025), Day::Day12));
+    }
+}
@@ -1,11 +1,26 @@
 //! Contains the [`Key`] struct that is used as an index by the `get_solution` function generated
 //! by the `autokey` crate.
+//!
+//! It also knows when each puzzle unlocks; see [`Key::unlock_time`], [`today`] and
+//! [`next_unlock`].
+//!
+//! All types here can be displayed and parsed back; keys look like `2015-01a`, though `2015/1/2`
+//! is accepted too. With the `serde` feature enabled, they are (de)serialized as those strings.
+
+mod calendar;
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+pub use crate::calendar::{next_unlock, today};
 
 use enum_iterator::IntoEnumIterator;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+use std::fmt::{self, Display};
+use std::str::FromStr;
 
-/// An index into the space of existing Advent of Code problems.
-#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+/// An index into the space of existing Advent of Code problems. Keys are ordered by event, then
+/// day, then part.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Key {
     /// The event to index into.
     pub event: Event,
@@ -15,33 +30,106 @@ pub struct Key {
     pub part: Part,
 }
 
-/// A specific Advent of Code event.
-#[derive(
-    Copy, Clone, Debug, Eq, Hash, PartialEq, IntoEnumIterator, IntoPrimitive, TryFromPrimitive,
-)]
-#[repr(u16)]
-pub enum Event {
-    AoC2015 = 2015,
-    AoC2016 = 2016,
-    AoC2017 = 2017,
-    AoC2018 = 2018,
-    AoC2019 = 2019,
-    AoC2020 = 2020,
-    AoC2021 = 2021,
+impl Key {
+    /// Whether there actually is a puzzle for this key; see [`Event::day_count`] and
+    /// [`Event::part_count`].
+    pub fn exists(&self) -> bool {
+        self.day as u8 <= self.event.day_count() && self.part as u8 <= self.event.part_count(self.day)
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}{}", self.event, self.day, self.part)
+    }
+}
+
+impl FromStr for Key {
+    type Err = anyhow::Error;
+
+    /// Parses either `2015-01a` or `2015/1/2`. Keys that don't exist (like `2015-25b`) are rejected.
+    fn from_str(s: &str) -> anyhow::Result<Key> {
+        let parts = match s.split_once('-') {
+            Some((event, rest)) if rest.len() > 1 => {
+                let (day, part) = rest.split_at(rest.len() - 1);
+                Some((event, day, part))
+            }
+            Some(_) => None,
+            None => match s.split('/').collect::<Vec<_>>()[..] {
+                [event, day, part] => Some((event, day, part)),
+                _ => None,
+            },
+        };
+
+        let (event, day, part) = parts.ok_or(anyhow::anyhow!(
+            "expected a key like `2015-01a` or `2015/1/2`, got `{}`",
+            s,
+        ))?;
+        let key = Key { event: event.parse()?, day: day.parse()?, part: part.parse()? };
+        if !key.exists() {
+            return Err(anyhow::anyhow!("there is no puzzle for {}", key));
+        }
+        Ok(key)
+    }
 }
 
+/// A specific Advent of Code event, identified by its year. Any year starting from 2015 is
+/// considered valid, so new events don't need any changes here.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Event(u16);
+
 impl Event {
-    pub fn parse(s: &str) -> Option<Vec<Event>> {
+    /// The year of the very first event.
+    pub const FIRST_YEAR: u16 = 2015;
+
+    /// Gets the event of the given year. Unlike [`Event::try_from`], this can be used in constants,
+    /// but panics for years before [`Event::FIRST_YEAR`].
+    pub const fn new(year: u16) -> Event {
+        assert!(year >= Event::FIRST_YEAR, "there were no Advent of Code events before 2015");
+        Event(year)
+    }
+
+    /// The year the event took place in.
+    pub fn year(self) -> u16 {
+        self.0
+    }
+
+    /// The number of days in the event. Events up to 2024 ran for 25 days, later ones only for 12.
+    pub fn day_count(self) -> u8 {
+        if self.0 < 2025 {
+            25
+        } else {
+            12
+        }
+    }
+
+    /// The number of parts of a day in the event. The last day only has one puzzle; its second
+    /// star is given for free once all the others are done.
+    pub fn part_count(self, day: Day) -> u8 {
+        if day as u8 == self.day_count() {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// All days in the event, in order.
+    pub fn days(self) -> impl Iterator<Item = Day> {
+        Day::into_enum_iter().take(self.day_count() as usize)
+    }
+
+    /// Parses events, given as years with either two or four digits; "." and "L" stand for all
+    /// and the latest of the `known` events, respectively.
+    pub fn parse(s: &str, known: &[Event]) -> Option<Vec<Event>> {
         let mut v = vec![];
 
         if s == "." {
-            v.extend(Event::into_enum_iter());
+            v.extend_from_slice(known);
         } else if s == "L" || s == "l" {
-            v.push(Event::into_enum_iter().last().unwrap());
+            v.push(*known.last()?);
         } else {
             for item in s.split(",") {
-                let num = 2000 + item.parse::<u16>().ok()?;
-                v.push(Event::try_from(num).ok()?);
+                v.push(item.parse().ok()?);
             }
         }
 
@@ -49,9 +137,48 @@ impl Event {
     }
 }
 
+impl TryFrom<u16> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(year: u16) -> anyhow::Result<Event> {
+        if year >= Event::FIRST_YEAR {
+            Ok(Event(year))
+        } else {
+            Err(anyhow::anyhow!("there was no Advent of Code event in {}", year))
+        }
+    }
+}
+
+impl From<Event> for u16 {
+    fn from(event: Event) -> u16 {
+        event.0
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Event {
+    type Err = anyhow::Error;
+
+    /// Parses a year with either two or four digits.
+    fn from_str(s: &str) -> anyhow::Result<Event> {
+        let year = match (s.len(), s.parse::<u16>()) {
+            (2, Ok(year)) => 2000 + year,
+            (4, Ok(year)) => year,
+            _ => return Err(anyhow::anyhow!("expected a year like `2015` or `15`, got `{}`", s)),
+        };
+        Event::try_from(year)
+    }
+}
+
 /// A day of an Advent of Code event.
 #[derive(
-    Copy, Clone, Debug, Eq, Hash, PartialEq, IntoEnumIterator, IntoPrimitive, TryFromPrimitive,
+    Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, IntoEnumIterator, IntoPrimitive,
+    TryFromPrimitive,
 )]
 #[repr(u8)]
 pub enum Day {
@@ -83,17 +210,21 @@ pub enum Day {
 }
 
 impl Day {
-    pub fn parse(s: &str) -> Option<Vec<Day>> {
+    /// Parses days of the given `event`; days past the end of it are rejected.
+    pub fn parse(event: Event, s: &str) -> Option<Vec<Day>> {
         let mut v = vec![];
 
         if s == "." {
-            v.extend(Day::into_enum_iter());
+            v.extend(event.days());
         } else if s == "L" || s == "l" {
-            v.push(Day::into_enum_iter().last().unwrap());
+            v.push(event.days().last().unwrap());
         } else {
             for item in s.split(",") {
-                let num = item.parse::<u8>().ok()?;
-                v.push(Day::try_from(num).ok()?);
+                let day: Day = item.parse().ok()?;
+                if day as u8 > event.day_count() {
+                    return None;
+                }
+                v.push(day);
             }
         }
 
@@ -101,9 +232,29 @@ impl Day {
     }
 }
 
+/// Days are displayed with two digits, like in the names of solution files.
+impl Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}", *self as u8)
+    }
+}
+
+impl FromStr for Day {
+    type Err = anyhow::Error;
+
+    /// Parses a day number, with or without a leading zero.
+    fn from_str(s: &str) -> anyhow::Result<Day> {
+        s.parse::<u8>()
+            .ok()
+            .and_then(|day| Day::try_from(day).ok())
+            .ok_or(anyhow::anyhow!("expected a day between 1 and 25, got `{}`", s))
+    }
+}
+
 /// A part of an Advent of Code day.
 #[derive(
-    Copy, Clone, Debug, Eq, Hash, PartialEq, IntoEnumIterator, IntoPrimitive, TryFromPrimitive,
+    Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, IntoEnumIterator, IntoPrimitive,
+    TryFromPrimitive,
 )]
 #[repr(u8)]
 pub enum Part {
@@ -119,11 +270,140 @@ impl Part {
             v.extend(Part::into_enum_iter());
         } else {
             for item in s.split(",") {
-                let num = item.parse::<u8>().ok()?;
-                v.push(Part::try_from(num).ok()?);
+                v.push(item.parse().ok()?);
             }
         }
 
         Some(v)
     }
 }
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Part::One => "a",
+            Part::Two => "b",
+        })
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    /// Parses either `a` and `b`, or `1` and `2`.
+    fn from_str(s: &str) -> anyhow::Result<Part> {
+        match s {
+            "a" | "1" => Ok(Part::One),
+            "b" | "2" => Ok(Part::Two),
+            _ => Err(anyhow::anyhow!("expected a part like `a` or `1`, got `{}`", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// All keys that exist in the given event.
+    fn keys_of(event: Event) -> impl Iterator<Item = Key> {
+        event.days().flat_map(move |day| {
+            Part::into_enum_iter().map(move |part| Key { event, day, part }).filter(Key::exists)
+        })
+    }
+
+    #[test]
+    fn keys_round_trip() {
+        for year in [2015, 2021, 2024, 2025, 2030] {
+            for key in keys_of(Event::new(year)) {
+                assert_eq!(key.to_string().parse::<Key>().unwrap(), key);
+            }
+        }
+    }
+
+    #[test]
+    fn keys_display_padded() {
+        let key = Key { event: Event::new(2015), day: Day::Day01, part: Part::One };
+        assert_eq!(key.to_string(), "2015-01a");
+    }
+
+    #[test]
+    fn keys_parse_slashes() {
+        let key = Key { event: Event::new(2015), day: Day::Day07, part: Part::Two };
+        assert_eq!("2015/7/2".parse::<Key>().unwrap(), key);
+        assert_eq!("15/07/b".parse::<Key>().unwrap(), key);
+        assert_eq!("2015-7b".parse::<Key>().unwrap(), key);
+    }
+
+    #[test]
+    fn keys_reject_bad_strings() {
+        for s in ["", "2015", "2015-", "2015-01", "2015-01c", "2015/1", "2015/1/2/3", "x-01a", "2015-xxa"] {
+            assert!(s.parse::<Key>().is_err(), "{} should be rejected", s);
+        }
+    }
+
+    #[test]
+    fn keys_reject_days_out_of_range() {
+        for s in ["2015-00a", "2015-26a", "2015/0/1", "2015/26/1", "2015-255a", "2015-256a"] {
+            assert!(s.parse::<Key>().is_err(), "{} should be rejected", s);
+        }
+    }
+
+    #[test]
+    fn keys_reject_second_part_of_last_day() {
+        assert!("2015-25a".parse::<Key>().is_ok());
+        assert!("2015-25b".parse::<Key>().is_err());
+        assert!("2024/25/2".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn keys_reject_events_before_2015() {
+        for s in ["2014-01a", "14-01a", "1999/1/1", "0000-01a"] {
+            assert!(s.parse::<Key>().is_err(), "{} should be rejected", s);
+        }
+        assert!(Event::try_from(2014).is_err());
+        assert!("2014".parse::<Event>().is_err());
+    }
+
+    #[test]
+    fn keys_reject_past_twelve_days_from_2025() {
+        assert!("2025-12a".parse::<Key>().is_ok());
+        assert!("2025-11b".parse::<Key>().is_ok());
+        assert!("2025-12b".parse::<Key>().is_err());
+        assert!("2025-13a".parse::<Key>().is_err());
+        assert!("2026/25/1".parse::<Key>().is_err());
+        assert!("2024-13a".parse::<Key>().is_ok());
+    }
+
+    #[test]
+    fn events_parse_short_years() {
+        assert_eq!("21".parse::<Event>().unwrap(), Event::new(2021));
+        assert_eq!("2021".parse::<Event>().unwrap(), Event::new(2021));
+        assert!("021".parse::<Event>().is_err());
+    }
+
+    #[test]
+    fn event_day_counts() {
+        assert_eq!(keys_of(Event::new(2015)).count(), 49);
+        assert_eq!(keys_of(Event::new(2025)).count(), 23);
+        assert_eq!(Event::new(2025).days().last(), Some(Day::Day12));
+    }
+
+    #[test]
+    fn days_parse_within_event() {
+        assert_eq!(Day::parse(Event::new(2025), "L"), Some(vec![Day::Day12]));
+        assert_eq!(Day::parse(Event::new(2025), "3,12"), Some(vec![Day::Day03, Day::Day12]));
+        assert_eq!(Day::parse(Event::new(2025), "13"), None);
+        assert_eq!(Day::parse(Event::new(2015), "25"), Some(vec![Day::Day25]));
+    }
+
+    #[test]
+    fn keys_ordered_by_event_day_part() {
+        let keys: Vec<Key> = ["2021-01a", "2015-25a", "2015-02a", "2015-01b", "2015-01a"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(sorted, keys.into_iter().rev().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,37 @@
+//! Serde support for the key types, which are (de)serialized as the strings they are displayed as.
+
+use crate::{Day, Event, Key, Part};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Implements `Serialize` via `Display`, and `Deserialize` via `FromStr`.
+macro_rules! impl_serde_via_str {
+    ($($ty:ty),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                from_str(deserializer)
+            }
+        }
+    )*};
+}
+
+impl_serde_via_str!(Key, Event, Day, Part);
+
+/// Deserializes a string, and parses it as a `T`.
+fn from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse().map_err(de::Error::custom)
+}
@@ -2,39 +2,104 @@
 
 mod solution;
 
-use crate::solution::Solution;
+use crate::solution::{ParsedDay, Solution};
 use itertools::Itertools;
-use keys::Part;
 use std::time::{Duration, Instant};
 
 fn main() {
-    let solutions = solutions_from_args().expect("could not parse arguments");
+    let mut args: Vec<_> = std::env::args().collect();
+    let compare = take_flag(&mut args, "--compare");
+    let skip_tags = take_option(&mut args, "--skip-tag").expect("missing tag after --skip-tag");
+    let only_tags = take_option(&mut args, "--only-tag").expect("missing tag after --only-tag");
+
+    if args.get(1).map(String::as_str) == Some("list") {
+        let events = keys::Event::parse(args.get(2).map_or(".", String::as_str), solutions::EVENTS)
+            .expect("could not parse arguments");
+        list_solutions(&events);
+        return;
+    }
+
+    if args.get(1).map(String::as_str) == Some("countdown") {
+        let (event, day) = keys::next_unlock();
+        let remaining = event
+            .unlock_time(day)
+            .duration_since(std::time::SystemTime::now())
+            .unwrap_or_default();
+        println!("[{}-{}]    unlocks in {}", event, day, countdown_as_string(remaining));
+        return;
+    }
+
+    let (keys, explicit) = match keys_from_args(&args) {
+        Ok(keys) => keys,
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
 
     let start = Instant::now();
+    let mut parsed = ParsedDay::default();
+    let mut parse_runtime = Duration::ZERO;
+    let mut not_compiled = Vec::new();
+
+    for key in keys {
+        let tags = solutions::SOLUTIONS
+            .iter()
+            .find(|info| info.key == key)
+            .map_or(&[][..], |info| info.tags);
 
-    for solution in solutions {
-        match solution.get_result() {
-            (Ok(a), runtime) => println!(
-                "[{}-{:02}{}]   {} (runtime: {}s)",
-                solution.key.event as u16,
-                solution.key.day as u8,
-                match solution.key.part {
-                    Part::One => 'a',
-                    Part::Two => 'b',
-                },
+        let has_any = |wanted: &[String]| tags.iter().any(|tag| wanted.iter().any(|w| w == tag));
+        if has_any(&skip_tags) || !(only_tags.is_empty() || has_any(&only_tags)) {
+            continue;
+        }
+
+        let solution = match Solution::new(key) {
+            Some(solution) => solution,
+            None => {
+                if !solutions::is_compiled(key.event) {
+                    if !not_compiled.contains(&key.event) {
+                        not_compiled.push(key.event);
+                        println!(
+                            "[{}]        not compiled (enable the \"aoc{0}\" feature)",
+                            key.event
+                        );
+                    }
+                } else if explicit {
+                    println!("[{}]   absent", key);
+                }
+                continue;
+            }
+        };
+
+        let (result, runtime, parsing) = solution.get_result(&mut parsed);
+
+        if let Some(parsing) = parsing {
+            parse_runtime = parsing;
+            println!(
+                "[{}-{}]    parsed input (runtime: {}s)",
+                solution.key.event,
+                solution.key.day,
+                duration_as_string(parsing)
+            );
+        }
+
+        let answer = result.as_ref().ok().cloned();
+
+        match result {
+            Ok(a) => println!(
+                "[{}]   {} (runtime: {}s)",
+                solution.key,
                 a,
                 duration_as_string(runtime)
             ),
-            (Err(e), _) => println!(
-                "[{}-{:02}{}]   FAILED: {}",
-                solution.key.event as u16,
-                solution.key.day as u8,
-                match solution.key.part {
-                    Part::One => 'a',
-                    Part::Two => 'b',
-                },
-                e
-            ),
+            Err(e) => println!("[{}]   FAILED: {}", solution.key, e),
+        }
+
+        if compare {
+            // Variants always run on the raw input, so include the parse step for a fair
+            // comparison.
+            let runtime = if solution.is_parsed() { runtime + parse_runtime } else { runtime };
+            print_variant_results(&solution, answer, runtime);
         }
     }
 
@@ -45,6 +110,82 @@ fn main() {
     );
 }
 
+/// Runs all named variants of `solution` and prints their results below the main one; `answer`
+/// and `runtime` are the results of the main solution. Flags variants that disagree with it, and
+/// shows how long each variant took relative to it.
+fn print_variant_results(solution: &Solution, answer: Option<String>, runtime: Duration) {
+    for (name, result, variant_runtime) in solution.get_variant_results() {
+        match result {
+            Ok(a) => println!(
+                "{:>10}   {}{} (runtime: {}s, {})",
+                name,
+                if answer.as_ref() == Some(&a) { "" } else { "MISMATCH: " },
+                a,
+                duration_as_string(variant_runtime),
+                relative_runtime(variant_runtime, runtime),
+            ),
+            Err(e) => println!("{:>10}   FAILED: {}", name, e),
+        }
+    }
+}
+
+/// How long `runtime` took compared to `baseline`, e.g. "0.50x". Just "n/a" if `baseline` was too
+/// short to measure, since dividing by it would give infinity or NaN.
+fn relative_runtime(runtime: Duration, baseline: Duration) -> String {
+    if baseline.is_zero() {
+        String::from("n/a")
+    } else {
+        format!("{:.2}x", runtime.as_secs_f64() / baseline.as_secs_f64())
+    }
+}
+
+/// Prints all registered solutions from the given events, with their descriptions and locations.
+fn list_solutions(events: &[keys::Event]) {
+    for info in solutions::SOLUTIONS {
+        if !events.contains(&info.key.event) {
+            continue;
+        }
+
+        println!(
+            "[{}]   {} ({}{}){}",
+            info.key,
+            info.description,
+            info.source,
+            if info.compiled { "" } else { ", not compiled" },
+            if info.tags.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", info.tags.join(", "))
+            },
+        );
+    }
+}
+
+/// Removes `flag` from `args`, returning whether it was there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Removes all occurences of `option` and the values following them from `args`, returning the
+/// values. `None` if one of them is missing its value.
+fn take_option(args: &mut Vec<String>, option: &str) -> Option<Vec<String>> {
+    let mut values = Vec::new();
+    while let Some(index) = args.iter().position(|arg| arg == option) {
+        args.remove(index);
+        if index == args.len() {
+            return None;
+        }
+        values.push(args.remove(index));
+    }
+    Some(values)
+}
+
 /// The number seconds in the [`std::time::Duration`], with three significant digits of fractional
 /// precision.
 fn duration_as_string(duration: Duration) -> String {
@@ -56,73 +197,134 @@ fn duration_as_string(duration: Duration) -> String {
     }
 }
 
-/// Reads command line arguments and returns the relevant solutions. The program accepts between
+/// A duration until a puzzle unlocks, down to the second, e.g. "3d 04:05:06".
+fn countdown_as_string(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}d {:02}:{:02}:{:02}",
+        seconds / (24 * 60 * 60),
+        seconds / (60 * 60) % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    )
+}
+
+/// Reads command line arguments and returns the relevant keys, as well as whether the days were
+/// given explicitly (rather than as "." or implied). The program accepts between
 /// 0 and 3 (inclusive) arguments, and the meaning changes depending on the amount.
 ///
-/// - "" (a special mode that runs only the latest day solution from the latest event)
+/// - "" (a special mode that runs today's puzzle while an event is running, and otherwise only the
+///   latest day solution from the latest event)
 /// - "day"
 /// - "event day"
 /// - "event day part"
 ///
-/// `event` defaults to "L" (the latest event known to the program). `day` has no default. `part`
-/// defaults to "." (all parts). You can supply a comma-separated list of numbers, "L" or "." to
-/// each argument.
+/// `event` defaults to "L" (the latest event with a folder in the solutions crate). `day` has no
+/// default. `part` defaults to "." (all parts). You can supply a comma-separated list of numbers,
+/// "L" or "." to each argument.
 ///
-/// `event` expects numbers in the range from 15 (AoC2015) and up; `day` expects numbers in the
-/// range from 1 to 25 (inclusive); `part` expects a 1 or 2.
+/// `event` expects years from 15 (AoC2015) and up, with either two or four digits; `day` expects
+/// numbers from 1 up to the number of days in the event (25 for most of them); `part` expects a 1
+/// or 2. The last day of an event only has part 1.
 ///
 /// When a list or "." are provided in multiple arguments, all possible combinations of those lists
-/// will be run.
-fn solutions_from_args() -> Option<Vec<Solution>> {
-    let args: Vec<_> = std::env::args().collect();
+/// will be run. Combinations that don't exist (like part 2 of the last day) are skipped silently
+/// if they come from a ".", but are an error if they were asked for explicitly.
+///
+/// Alternatively, "list [event]" lists all solutions (from the given events, all by default) with
+/// their descriptions, and "countdown" shows how long it is until the next puzzle unlocks, instead
+/// of running anything.
+///
+/// Additionally, "--compare" can be passed anywhere, which also runs all named variants of each
+/// solution, checking that they agree with it and showing their relative speed. "--skip-tag tag"
+/// skips solutions with the given tag, while "--only-tag tag" only runs solutions with it; both
+/// can be given multiple times.
+fn keys_from_args(args: &[String]) -> anyhow::Result<(Vec<keys::Key>, bool)> {
+    let invalid = || anyhow::anyhow!("could not parse arguments");
+
+    // No argument during an event: run today's puzzle, whether there's a solution for it yet or
+    // not.
+    let today = keys::today();
+    if args.len() == 1 && !today.is_empty() {
+        return Ok((today, true));
+    }
 
     let (events, days, parts) = match args.len() {
-        1 => {
-            // No argument. Run the highest day solution available from the default event (the
-            // as if you supplied "L" for the year).
-            let event = keys::Event::parse("L")?;
-            let mut days = keys::Day::parse(".")?;
-            days.retain(|&d| {
-                Solution::new(keys::Key {
-                    event: event[0],
-                    day: d,
-                    part: Part::One,
-                })
-                .is_some()
-            });
-            days.reverse();
-            days.truncate(1);
-
-            (event, days, keys::Part::parse(".")?)
-        }
-        2 => (
-            // One argument: day, assuming some convenient year and all parts.
-            keys::Event::parse("L")?,
-            keys::Day::parse(&args[1])?,
-            keys::Part::parse(".")?,
-        ),
-        3 => (
-            // Two arguments: year and day, assuming all parts.
-            keys::Event::parse(&args[1])?,
-            keys::Day::parse(&args[2])?,
-            keys::Part::parse(".")?,
-        ),
-        4 => (
-            // Three arguments: year, day and part.
-            keys::Event::parse(&args[1])?,
-            keys::Day::parse(&args[2])?,
-            keys::Part::parse(&args[3])?,
-        ),
-        _ => return None,
+        // No argument. Run the highest day solution available from the default event (as if you
+        // supplied "L" for the year).
+        1 => ("L", None, "."),
+        // One argument: day, assuming some convenient year and all parts.
+        2 => ("L", Some(args[1].as_str()), "."),
+        // Two arguments: year and day, assuming all parts.
+        3 => (args[1].as_str(), Some(args[2].as_str()), "."),
+        // Three arguments: year, day and part.
+        4 => (args[1].as_str(), Some(args[2].as_str()), args[3].as_str()),
+        _ => return Err(invalid()),
     };
 
-    Some(
-        events
-            .iter()
-            .cartesian_product(&days)
-            .cartesian_product(&parts)
-            .map(|((&event, &day), &part)| keys::Key { event, day, part })
-            .filter_map(Solution::new)
-            .collect(),
-    )
+    let explicit = days.is_some_and(|days| days != ".");
+    let wildcard = [Some(events), days, Some(parts)].contains(&Some("."));
+    let parts = keys::Part::parse(parts).ok_or_else(invalid)?;
+
+    let mut keys = Vec::new();
+    for event in keys::Event::parse(events, solutions::EVENTS).ok_or_else(invalid)? {
+        let days = match days {
+            Some(days) => keys::Day::parse(event, days).ok_or_else(invalid)?,
+            None => solutions::SOLUTIONS
+                .iter()
+                .filter(|info| info.compiled && info.key.event == event)
+                .map(|info| info.key.day)
+                .next_back()
+                .into_iter()
+                .collect(),
+        };
+
+        for (&day, &part) in days.iter().cartesian_product(&parts) {
+            let key = keys::Key { event, day, part };
+            if key.exists() {
+                keys.push(key);
+            } else if !wildcard {
+                return Err(anyhow::anyhow!("{} doesn't exist", key));
+            }
+        }
+    }
+
+    Ok((keys, explicit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs `keys_from_args` on a command line, given without the program name.
+    fn keys(args: &str) -> anyhow::Result<Vec<String>> {
+        let args: Vec<_> =
+            std::iter::once("runner").chain(args.split(' ')).map(String::from).collect();
+        let (keys, _) = keys_from_args(&args)?;
+        Ok(keys.iter().map(|key| key.to_string()).collect())
+    }
+
+    #[test]
+    fn explicit_keys_must_exist() {
+        assert_eq!(keys("15 25 1").unwrap(), vec!["2015-25a"]);
+        assert_eq!(keys("15 25 2").unwrap_err().to_string(), "2015-25b doesn't exist");
+        assert_eq!(keys("15 24,25 2").unwrap_err().to_string(), "2015-25b doesn't exist");
+        assert!(keys("15 26 1").is_err());
+    }
+
+    #[test]
+    fn wildcards_skip_missing_keys() {
+        assert_eq!(keys("15 25").unwrap(), vec!["2015-25a"]);
+        assert_eq!(keys("15 25 .").unwrap(), vec!["2015-25a"]);
+        assert_eq!(keys("15 . 2").unwrap().len(), 24);
+    }
+
+    #[test]
+    fn relative_runtimes() {
+        let ms = Duration::from_millis;
+        assert_eq!(relative_runtime(ms(5), ms(10)), "0.50x");
+        assert_eq!(relative_runtime(ms(30), ms(10)), "3.00x");
+        assert_eq!(relative_runtime(ms(5), Duration::ZERO), "n/a");
+        assert_eq!(relative_runtime(Duration::ZERO, Duration::ZERO), "n/a");
+    }
 }
@@ -1,41 +1,120 @@
 use anyhow::Result;
-use keys::Key;
-use std::fs::File;
-use std::io::BufRead;
+use keys::{Day, Event, Key};
+use solutions::{Input, ParsedInput};
+use std::any::Any;
 use std::time::{Duration, Instant};
 
+/// The signature shared by all solution functions.
+type SolutionFn = fn(&[&str]) -> Result<String>;
+
+/// The parse step of a solution that takes parsed input.
+type ParserFn = fn(&[&str]) -> Result<ParsedInput>;
+
+/// The part of a solution that takes parsed input which runs after the parse step.
+type ParsedSolutionFn = fn(&dyn Any) -> Result<String>;
+
+/// The parsed input of the most recently parsed day, along with the path of the `parse` function
+/// that produced it, so that running both parts of a day only parses it once if they share it.
+#[derive(Default)]
+pub struct ParsedDay(Option<((Event, Day, &'static str), ParsedInput)>);
+
 pub struct Solution {
     pub key: Key,
-    pub solution: fn(&[&str]) -> Result<String>,
-    data_path: &'static str,
+    pub solution: SolutionFn,
+    pub variants: &'static [(&'static str, SolutionFn)],
+    /// The separate parse step (along with the path of the `parse` function) and solution proper,
+    /// if the solution takes parsed input.
+    parsed: Option<((&'static str, ParserFn), ParsedSolutionFn)>,
+    input: Input,
 }
 
 impl Solution {
     pub fn new(key: Key) -> Option<Solution> {
-        solutions::get_solution(key).map(|(solution, data_path)| Solution {
+        solutions::get_solution(key).map(|(solution, input)| Solution {
             key,
             solution,
-            data_path,
+            variants: solutions::get_variants(key),
+            parsed: solutions::get_parser(key).zip(solutions::get_parsed_solution(key)),
+            input,
         })
     }
 
-    pub fn get_result(&self) -> (Result<String>, Duration) {
-        let raw_input: Vec<_> = {
-            let file = File::open(self.data_path)
-                .expect(&format!("failed to open data file '{}'", self.data_path));
-
-            std::io::BufReader::new(file)
-                .lines()
-                .map(|l| l.unwrap())
-                .collect()
+    /// Runs the solution, returning its result and runtime.
+    ///
+    /// If the solution takes parsed input, the input is taken from `parsed` if it already holds
+    /// this day as parsed by the same `parse` function, and parsed and stored in it otherwise. The
+    /// time spent parsing is returned separately, and is `None` if no parsing happened.
+    pub fn get_result(&self, parsed: &mut ParsedDay) -> (Result<String>, Duration, Option<Duration>) {
+        let ((parser_path, parser), solution) = match self.parsed {
+            Some(parsed) => parsed,
+            None => {
+                let (result, runtime) = run(self.solution, &self.read_input());
+                return (result, runtime, None);
+            }
         };
 
-        let ref_input: Vec<_> = raw_input.iter().map(|s| &**s).collect();
+        let day = (self.key.event, self.key.day, parser_path);
+        let mut parse_runtime = None;
+
+        if !matches!(parsed.0, Some((parsed_day, _)) if parsed_day == day) {
+            let raw_input = self.read_input();
+            let ref_input: Vec<_> = raw_input.iter().map(|s| &**s).collect();
+
+            let start = Instant::now();
+            let returned = parser(&ref_input);
+            parse_runtime = Some(Instant::now().duration_since(start));
+
+            match returned {
+                Ok(input) => parsed.0 = Some((day, input)),
+                Err(e) => {
+                    parsed.0 = None;
+                    return (Err(e), Duration::ZERO, parse_runtime);
+                }
+            }
+        }
+
+        let input = &*parsed.0.as_ref().unwrap().1;
 
         let start = Instant::now();
-        let returned = (self.solution)(&ref_input);
+        let returned = solution(input);
         let end = Instant::now();
 
-        (returned, end.duration_since(start))
+        (returned, end.duration_since(start), parse_runtime)
+    }
+
+    /// Whether the solution parses its input in a separate step.
+    pub fn is_parsed(&self) -> bool {
+        self.parsed.is_some()
+    }
+
+    /// Runs all named variants of this solution, in order. The input is only read once.
+    pub fn get_variant_results(&self) -> Vec<(&'static str, Result<String>, Duration)> {
+        let input = self.read_input();
+        self.variants
+            .iter()
+            .map(|&(name, variant)| {
+                let (result, runtime) = run(variant, &input);
+                (name, result, runtime)
+            })
+            .collect()
+    }
+
+    /// Reads the input for this solution, line by line.
+    fn read_input(&self) -> Vec<String> {
+        self.input.read().unwrap_or_else(|_| match self.input {
+            Input::Path(path) => panic!("failed to open data file '{}'", path),
+            Input::Embedded(_) => unreachable!("reading embedded input can't fail"),
+        })
     }
 }
+
+/// Runs a single solution function on `raw_input`, timing it.
+fn run(solution: SolutionFn, raw_input: &[String]) -> (Result<String>, Duration) {
+    let ref_input: Vec<_> = raw_input.iter().map(|s| &**s).collect();
+
+    let start = Instant::now();
+    let returned = solution(&ref_input);
+    let end = Instant::now();
+
+    (returned, end.duration_since(start))
+}
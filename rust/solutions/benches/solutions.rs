@@ -0,0 +1,75 @@
+//! Criterion benchmarks for all registered solutions, with one group per event and one benchmark
+//! per day and part, e.g. "2021/15/1". Run `cargo bench -- 2021/15` to only benchmark one day.
+//!
+//! Each benchmark runs the same function the runner would, on the real input. Days with a separate
+//! parse step have it done once up front, so only the solution proper is timed.
+
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, Criterion};
+use criterion::measurement::WallTime;
+use itertools::Itertools;
+use solutions::SolutionInfo;
+
+/// Solutions tagged as slow take seconds per iteration; the default of 100 samples would take
+/// forever for those.
+const SLOW_SAMPLE_SIZE: usize = 10;
+
+/// The default amount of samples per benchmark, as per criterion.
+const DEFAULT_SAMPLE_SIZE: usize = 100;
+
+fn bench_solutions(c: &mut Criterion) {
+    let compiled = solutions::SOLUTIONS.iter().filter(|info| info.compiled);
+
+    for (event, infos) in &compiled.group_by(|info| info.key.event) {
+        let mut group = c.benchmark_group(event.year().to_string());
+
+        for info in infos {
+            bench_solution(&mut group, info);
+        }
+
+        group.finish();
+    }
+}
+
+/// Adds the benchmark for a single solution to the `group` of its event. Skips it (with a
+/// message) if the input is missing or can't be parsed.
+fn bench_solution(group: &mut BenchmarkGroup<WallTime>, info: &SolutionInfo) {
+    let id = format!("{}/{}", info.key.day as u8, info.key.part as u8);
+
+    let (solution, input) = match solutions::get_solution(info.key) {
+        Some(solution) => solution,
+        None => return,
+    };
+
+    let raw_input = match input.read() {
+        Ok(raw_input) => raw_input,
+        Err(err) => {
+            eprintln!("skipping {}/{}: failed to read input: {}", info.key.event.year(), id, err);
+            return;
+        }
+    };
+    let ref_input: Vec<_> = raw_input.iter().map(|s| &**s).collect();
+
+    group.sample_size(if info.tags.contains(&"slow") {
+        SLOW_SAMPLE_SIZE
+    } else {
+        DEFAULT_SAMPLE_SIZE
+    });
+
+    let parser = solutions::get_parser(info.key);
+    match parser.zip(solutions::get_parsed_solution(info.key)) {
+        Some(((_, parse), solution)) => match parse(&ref_input) {
+            Ok(parsed) => {
+                group.bench_function(&id, |b| b.iter(|| solution(&*parsed)));
+            }
+            Err(err) => {
+                eprintln!("skipping {}/{}: failed to parse input: {}", info.key.event.year(), id, err);
+            }
+        },
+        None => {
+            group.bench_function(&id, |b| b.iter(|| solution(&ref_input)));
+        }
+    }
+}
+
+criterion_group!(benches, bench_solutions);
+criterion_main!(benches);
@@ -0,0 +1,14 @@
+//! `autokey::events!` reads the source folder while expanding, which cargo doesn't know about; so
+//! adding or removing a solution file or event folder wouldn't otherwise trigger a rebuild. Watching
+//! the whole folder fixes that. The same goes for the features declared in Cargo.toml, which decide
+//! which events are gated.
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    // Which inputs exist only matters for the build if they get embedded.
+    if std::env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        println!("cargo:rerun-if-changed=../data");
+    }
+}
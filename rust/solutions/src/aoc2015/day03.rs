@@ -1,29 +1,29 @@
-use std::collections::HashMap;
+use crate::util::plane::Plane;
 
 // Follow the instructions, count visited positions.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    let mut visited = HashMap::new();
+    let mut visited = Plane::new();
     walk(&mut visited, input[0].chars());
-    Ok(visited.keys().count().to_string())
+    Ok(visited.len().to_string())
 }
 
 /// Split up the instructions into two sequences, by alternating elements.
 /// Run these separately, and count the number of positions they visit together.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    let mut visited = HashMap::new();
+    let mut visited = Plane::new();
     let (a, b): (Vec<_>, Vec<_>) = input[0].chars().enumerate().partition(|(i, _)| i % 2 == 0);
     walk(&mut visited, a.iter().map(|&(_, c)| c));
     walk(&mut visited, b.iter().map(|&(_, c)| c));
-    Ok(visited.keys().count().to_string())
+    Ok(visited.len().to_string())
 }
 
-/// Executes a sequence of instructions, logging all positions that were visited.
-fn walk(visited: &mut HashMap<(i32, i32), usize>, instructions: impl Iterator<Item = char>) {
+/// Executes a sequence of instructions, counting the visits to each position.
+fn walk(visited: &mut Plane<usize>, instructions: impl Iterator<Item = char>) {
     let mut position = (0, 0);
-    *visited.entry(position).or_insert(0) += 1;
+    *visited.get_or_default(position) += 1;
     for c in instructions {
         step(&mut position, c);
-        *visited.entry(position).or_insert(0) += 1;
+        *visited.get_or_default(position) += 1;
     }
 }
 
@@ -1,3 +1,5 @@
+autokey::day_tags!("brute-force");
+
 /// Find the lowest i for which md5("{input}{i}") starts with at least 5 zeroes.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     for i in 1.. {
@@ -13,6 +15,7 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 }
 
 /// Find the lowest i for which md5("{input}{i}") starts with at least 6 zeroes.
+#[autokey::tags("slow")]
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     for i in 1.. {
         let hash = md5::compute(format!("{}{}", input[0], i)).0;
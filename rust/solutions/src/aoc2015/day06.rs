@@ -1,11 +1,13 @@
-use regex::Regex;
+use crate::scan;
+use crate::util::grid::Grid;
 use std::ops::Range;
 
 /// Use instructions to turn on/off or toggle blocks of lights. Count the amount of lights that
 /// are on.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    let mut lights = vec![false; 1_000_000];
-    for instruction in input.iter().map(|line| parse_line(line).unwrap()) {
+    let mut lights = Grid::new(1000, 1000, false);
+    for line in input {
+        let instruction = parse_line(line)?;
         apply_instruction(&mut lights, instruction, |val, prev| match val {
             Some(b) => b,
             None => !prev,
@@ -17,8 +19,9 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 
 /// Use instructions to manipulate the brightness of lights.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    let mut lights = vec![0u64; 1_000_000];
-    for instruction in input.iter().map(|line| parse_line(line).unwrap()) {
+    let mut lights = Grid::new(1000, 1000, 0u64);
+    for line in input {
+        let instruction = parse_line(line)?;
         apply_instruction(&mut lights, instruction, |val, prev| match val {
             Some(true) => prev + 1,
             Some(false) => prev.saturating_sub(1),
@@ -31,37 +34,31 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
 
 /// For all lights mentioned in the instruction, replaces their value with the result of calling
 /// morph with the instruction and their old value.
-fn apply_instruction<E: Copy>(lights: &mut Vec<E>, instruction: Instruction, morph: fn(Option<bool>, E) -> E) {
+fn apply_instruction<E: Copy>(lights: &mut Grid<E>, instruction: Instruction, morph: fn(Option<bool>, E) -> E) {
     let Instruction { val, xs, ys } = instruction;
 
-    for y in ys {
-        for x in xs.clone() {
-            let idx = 1000 * y + x;
-            lights[idx] = morph(val, lights[idx]);
+    // Going through row slices rather than indexing each light lets the loop be vectorized.
+    for row in lights.rows_mut().skip(ys.start).take(ys.len()) {
+        for light in &mut row[xs.clone()] {
+            *light = morph(val, *light);
         }
     }
 }
 
 /// Produces an [`Instruction`] from a line of puzzle input.
-fn parse_line(line: &str) -> Option<Instruction> {
-    lazy_static::lazy_static! {
-        static ref INSTRUCTION: Regex = Regex::new(r"^(turn on|turn off|toggle) (\d+),(\d+) through (\d+),(\d+)$").unwrap();
-    }
+fn parse_line(line: &str) -> anyhow::Result<Instruction> {
+    let (command, left, top, right, bottom) = scan!(
+        line,
+        "{turn on|turn off|toggle} {},{} through {},{}" => &str, usize, usize, usize, usize
+    )?;
+
+    let val = match command {
+        "turn on" => Some(true),
+        "turn off" => Some(false),
+        _ => None,
+    };
 
-    INSTRUCTION.captures(line).map(|captures| {
-        let val = match captures.get(1).unwrap().as_str() {
-            "turn on" => Some(true),
-            "turn off" => Some(false),
-            "toggle" => None,
-            _ => unreachable!(), // only the three cases above are ever produced by the regex
-        };
-        let left = captures.get(2).and_then(|c| c.as_str().parse().ok()).unwrap();
-        let top = captures.get(3).and_then(|c| c.as_str().parse().ok()).unwrap();
-        let right = 1 + captures.get(4).and_then(|c| c.as_str().parse::<usize>().ok()).unwrap();
-        let bottom = 1 + captures.get(5).and_then(|c| c.as_str().parse::<usize>().ok()).unwrap();
-        
-        Instruction { val, xs: left..right, ys: top..bottom, }
-    })
+    Ok(Instruction { val, xs: left..right + 1, ys: top..bottom + 1 })
 }
 
 /// An instruction from the puzzle input.
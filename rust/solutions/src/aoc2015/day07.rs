@@ -1,33 +1,34 @@
 use std::collections::HashMap;
 
 /// Resolve all wire connections in the input, return value of wire "a".
-pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    let mut vals = HashMap::new();
-    let mut connections = input.iter().map(|line| parse_line(line).unwrap()).collect::<Vec<_>>();
-    while !connections.is_empty() {
-        connections.retain(|c| c.apply(&mut vals).is_none())
-    }
-    Ok(vals.get("a").unwrap().to_string())
+pub fn part1(connections: &[Connection]) -> anyhow::Result<String> {
+    Ok(resolve(connections, HashMap::new()).to_string())
 }
 
 /// Take the result from part 1, set it as the initial value of wire "b", run it all again,
 /// and return the new value of wire "a".
-pub fn part2(input: &[&str]) -> anyhow::Result<String> {
+pub fn part2(connections: &[Connection]) -> anyhow::Result<String> {
     let mut vals = HashMap::new();
-    let mut connections = input.iter().map(|line| parse_line(line).unwrap()).collect::<Vec<_>>();
-    while !connections.is_empty() {
-        connections.retain(|c| c.apply(&mut vals).is_none())
-    }
+    vals.insert(String::from("b"), resolve(connections, HashMap::new()));
+    Ok(resolve(connections, vals).to_string())
+}
 
-    let a = *vals.get("a").unwrap();
-    vals.clear();
-    vals.insert(String::from("b"), a);
-    let mut connections = input.iter().map(|line| parse_line(line).unwrap()).collect::<Vec<_>>();
-    while !connections.is_empty() {
-        connections.retain(|c| c.apply(&mut vals).is_none())
-    }
+/// Parses the puzzle input into a list of [`Connection`]s.
+pub fn parse(input: &[&str]) -> anyhow::Result<Vec<Connection>> {
+    input
+        .iter()
+        .map(|line| parse_line(line).ok_or(anyhow::anyhow!("failed to parse input")))
+        .collect()
+}
 
-    Ok(vals.get("a").unwrap().to_string())
+/// Applies all `connections` until every wire has a value, starting from the already known wire
+/// values in `vals`. Returns the value of wire "a".
+fn resolve(connections: &[Connection], mut vals: HashMap<String, u16>) -> u16 {
+    let mut remaining: Vec<_> = connections.iter().collect();
+    while !remaining.is_empty() {
+        remaining.retain(|c| c.apply(&mut vals).is_none())
+    }
+    *vals.get("a").unwrap()
 }
 
 /// Produces a [`Connection`] from a line of puzzle input.
@@ -53,7 +54,7 @@ fn parse_line(line: &str) -> Option<Connection> {
 }
 
 /// A wire connection described by a line of puzzle input.
-struct Connection {
+pub struct Connection {
     source: Source,
     target: String,
 }
@@ -1,11 +1,11 @@
 /// See [`superfluous_characters_when_reducing`].
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    Ok(input.iter().map(|s| superfluous_characters_when_reducing(*s)).sum::<u32>().to_string())
+    Ok(input.iter().map(|s| superfluous_characters_when_reducing(s)).sum::<u32>().to_string())
 }
 
 /// See [`superfluous_characters_when_expanding`].
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    Ok(input.iter().map(|s| superfluous_characters_when_expanding(*s)).sum::<u32>().to_string())
+    Ok(input.iter().map(|s| superfluous_characters_when_expanding(s)).sum::<u32>().to_string())
 }
 
 /// Counts how many more characters there are in the string literal respresentation of a string
@@ -5,8 +5,8 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     let mut vec2 = vec![0; vec1.len()];
 
     for _ in 0..20 {
-        step_look_and_say(&mut vec1, &mut vec2);
-        step_look_and_say(&mut vec2, &mut vec1);
+        step_look_and_say(&vec1, &mut vec2);
+        step_look_and_say(&vec2, &mut vec1);
     }
 
     Ok(vec1.len().to_string())
@@ -19,8 +19,8 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     let mut vec2 = vec![0; vec1.len()];
 
     for _ in 0..25 {
-        step_look_and_say(&mut vec1, &mut vec2);
-        step_look_and_say(&mut vec2, &mut vec1);
+        step_look_and_say(&vec1, &mut vec2);
+        step_look_and_say(&vec2, &mut vec1);
     }
 
     Ok(vec1.len().to_string())
@@ -30,17 +30,17 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
 /// `target`.
 /// # Panics
 /// When `source` is empty.
-fn step_look_and_say(source: &mut Vec<u8>, target: &mut Vec<u8>) {
+fn step_look_and_say(source: &[u8], target: &mut Vec<u8>) {
     target.clear();
     let mut last = source[0];
     let mut run = 1;
-    for i in 1..source.len() {
-        if source[i] == last {
+    for &digit in &source[1..] {
+        if digit == last {
             run += 1;
         } else {
             target.push(run);
             target.push(last);
-            last = source[i];
+            last = digit;
             run = 1;
         }
     }
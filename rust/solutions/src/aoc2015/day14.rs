@@ -1,13 +1,15 @@
-use regex::Regex;
+use crate::scan;
 
 /// Find the best result of the race.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    Ok(input.iter().map(|&s| distance_after_time(parse_line(s).unwrap(), 2503)).max().unwrap().to_string())
+    let reindeer_stats = input.iter().map(|s| parse_line(s)).collect::<anyhow::Result<Vec<_>>>()?;
+    let distances = reindeer_stats.into_iter().map(|stats| distance_after_time(stats, 2503));
+    Ok(distances.max().ok_or(anyhow::anyhow!("no reindeer"))?.to_string())
 }
 
 /// Using a revised scoring system, find the best result of the race.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    let reindeer_stats: Vec<_> = input.iter().map(|&s| parse_line(s).unwrap()).collect();
+    let reindeer_stats = input.iter().map(|s| parse_line(s)).collect::<anyhow::Result<Vec<_>>>()?;
     Ok(points_after_time(&reindeer_stats, 2503).to_string())
 }
 
@@ -49,15 +51,11 @@ fn points_after_time(reindeer_stats: &[(u32, u32, u32)], seconds: u32) -> u32 {
 }
 
 /// Extracts the speed, travel time and rest time from a line of puzzle input.
-fn parse_line(s: &str) -> Option<(u32, u32, u32)> {
-    lazy_static::lazy_static! {
-        static ref RE: Regex = Regex::new(r"\d+").unwrap();
-    }
-
-    let mut matches = RE.find_iter(s);
-    let speed = matches.next().and_then(|m| m.as_str().parse().ok())?;
-    let travel_time = matches.next().and_then(|m| m.as_str().parse().ok())?;
-    let rest_time = matches.next().and_then(|m| m.as_str().parse().ok())?;
+fn parse_line(s: &str) -> anyhow::Result<(u32, u32, u32)> {
+    let (_, speed, travel_time, rest_time) = scan!(
+        s,
+        "{} can fly {} km/s for {} seconds, but then must rest for {} seconds." => &str, u32, u32, u32
+    )?;
 
-    Some((speed, travel_time, rest_time))
+    Ok((speed, travel_time, rest_time))
 }
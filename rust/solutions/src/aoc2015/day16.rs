@@ -1,4 +1,4 @@
-use regex::Regex;
+use crate::scan;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
@@ -7,13 +7,14 @@ use std::collections::HashMap;
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     let facts = sue_facts();
 
-    for (n, (k1, v1), (k2, v2), (k3, v3)) in input.iter().map(|s| parse_line(s)) {
+    for line in input {
+        let (n, (k1, v1), (k2, v2), (k3, v3)) = parse_line(line)?;
         if facts[k1].0 == v1 && facts[k2].0 == v2 && facts[k3].0 == v3 {
             return Ok(n.to_string());
         }
     }
 
-    unreachable!()
+    Err(anyhow::anyhow!("no matching Sue"))
 }
 
 /// Find the Sue for which all three facts match sue_facts table (including the Ordering
@@ -21,7 +22,8 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     let facts = sue_facts();
 
-    for (n, (k1, v1), (k2, v2), (k3, v3)) in input.iter().map(|s| parse_line(s)) {
+    for line in input {
+        let (n, (k1, v1), (k2, v2), (k3, v3)) = parse_line(line)?;
         let match1 = v1.cmp(&facts[k1].0) == facts[k1].1;
         let match2 = v2.cmp(&facts[k2].0) == facts[k2].1;
         let match3 = v3.cmp(&facts[k3].0) == facts[k3].1;
@@ -30,26 +32,23 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
         }
     }
 
-    unreachable!()
+    Err(anyhow::anyhow!("no matching Sue"))
 }
 
-/// Parses a line of puzzle input into a Sue entry.
-fn parse_line(s: &str) -> (usize, (&str, u32), (&str, u32), (&str, u32)) {
-    lazy_static::lazy_static! {
-        static ref RE: Regex = Regex::new(r"^Sue (\d+): ([^:]+): (\d+), ([^:]+): (\d+), ([^:]+): (\d+)$").unwrap();
-    }
-
-    let cs = RE.captures(s).unwrap();
+/// A Sue entry: her number, followed by the three facts remembered about her.
+type Sue<'a> = (usize, (&'a str, u32), (&'a str, u32), (&'a str, u32));
 
+/// Parses a line of puzzle input into a Sue entry.
+fn parse_line(s: &str) -> anyhow::Result<Sue<'_>> {
     // Today I realized that I don't really need to allocate Strings. The puzzle input lives for
     // the entire scope of the puzzle solution code, after all. I knew there were gonna be benefits
     // to coercing it into &[&str] :^)
-    (
-        cs[1].parse().unwrap(),
-        (&s[cs.get(2).unwrap().range()], cs[3].parse().unwrap()),
-        (&s[cs.get(4).unwrap().range()], cs[5].parse().unwrap()),
-        (&s[cs.get(6).unwrap().range()], cs[7].parse().unwrap())
-    )
+    let (n, k1, v1, k2, v2, k3, v3) = scan!(
+        s,
+        "Sue {}: {}: {}, {}: {}, {}: {}" => usize, &str, u32, &str, u32, &str, u32
+    )?;
+
+    Ok((n, (k1, v1), (k2, v2), (k3, v3)))
 }
 
 /// Contains all the information gained from the MFCSAM.
@@ -1,68 +1,50 @@
-use std::convert::TryInto;
+use crate::util::automaton::{self, Automaton};
+use crate::util::grid::Grid;
 
 /// Run a cellular automaton for 100 steps, count the live cells.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    let mut grid1 = parse_input(input);
-    let mut grid2 = vec![false; grid1.len()];
-    let rules = |_, b, n| (b && n == 2) || n == 3;
+    let grid = parse_input(input).ok_or(anyhow::anyhow!("failed to parse input"))?;
+    let mut lights = Automaton::new(grid, false, automaton::MOORE);
 
-    for _ in 0..50 {
-        step_with_rules(&grid1, &mut grid2, rules);
-        step_with_rules(&grid2, &mut grid1, rules);
+    for _ in 0..100 {
+        lights.step(|_, &b, neighbours| life(b, neighbours));
     }
 
-    Ok(grid1.iter().copied().filter(|&b| b).count().to_string())
+    Ok(lights.cells().filter(|&&b| b).count().to_string())
 }
 
 /// Run a cellular automaton for 100 steps, count the live cells. The rules for the automaton
 /// stipulate that the corner cells are *always* alive.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    let mut grid1 = parse_input(input);
-    let mut grid2 = vec![false; grid1.len()];
-    let rules = |i, b, n| [0, 99, 9900, 9999].contains(&i) || (b && n == 2) || n == 3;
-    grid1[0] = true;
-    grid1[99] = true;
-    grid1[9900] = true;
-    grid1[9999] = true;
-
-    for _ in 0..50 {
-        step_with_rules(&grid1, &mut grid2, rules);
-        step_with_rules(&grid2, &mut grid1, rules);
+    let mut grid = parse_input(input).ok_or(anyhow::anyhow!("failed to parse input"))?;
+    let (w, h) = (grid.width() - 1, grid.height() - 1);
+    for corner in [(0, 0), (w, 0), (0, h), (w, h)] {
+        grid[corner] = true;
     }
 
-    Ok(grid1.iter().copied().filter(|&b| b).count().to_string())
-}
+    let (w, h) = (w as i32, h as i32);
+    let corners = [(0, 0), (w, 0), (0, h), (w, h)];
+    let mut lights = Automaton::new(grid, false, automaton::MOORE);
 
-/// Steps the automaton using the given ruleset.
-fn step_with_rules(source: &[bool], target: &mut [bool], rules: fn(usize, bool, u8) -> bool) {
-    for i in 0..target.len() {
-        target[i] = rules(i, source[i], true_neighbours(&source, i));
+    for _ in 0..100 {
+        lights.step(|p, &b, neighbours| corners.contains(&p) || life(b, neighbours));
     }
-}
 
-/// Counts the number of neighbour that are true.
-fn true_neighbours(grid: &[bool], i: usize) -> u8 {    
-    // Offsets that move straight up or down are always ok (they'll just be out of bounds later)
-    let indices = [-100, 100].iter()
-        // only include offsets that end up further left if we're not at the left edge
-        .chain(if i % 100 != 0 { [-101, -1, 99].iter() } else { [].iter() })
-        // only include offsets that end up further right if we're not at the right edge
-        .chain(if i % 100 != 99 { [-99, 1, 101].iter() } else { [].iter() });
+    Ok(lights.cells().filter(|&&b| b).count().to_string())
+}
 
-    indices
-        .filter(|&d| *(i as i32 + d).try_into().ok().and_then(|index: usize| grid.get(index)).unwrap_or(&false))
-        .count() as u8
+/// The rules of the automaton: whether a cell will be alive, given whether it is now, and its
+/// neighbours.
+fn life(alive: bool, neighbours: &[bool]) -> bool {
+    let n = neighbours.iter().filter(|&&b| b).count();
+    (alive && n == 2) || n == 3
 }
 
 /// Parses the input into a grid of bools.
-fn parse_input(input: &[&str]) -> Vec<bool> {
-    let mut result = vec![false; 100 * 100];
-    for (i, &v) in input.iter().enumerate() {
-        for (j, c) in v.chars().enumerate() {
-            if c == '#' {
-                result[i * 100 + j] = true;
-            }
-        }
-    }
-    result
+fn parse_input(input: &[&str]) -> Option<Grid<bool>> {
+    Grid::parse(input, |b| match b {
+        b'#' => Some(true),
+        b'.' => Some(false),
+        _ => None,
+    })
 }
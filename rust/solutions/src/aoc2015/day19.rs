@@ -16,6 +16,7 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 /// Find the number of steps required to build the target molecule starting from `e`. Though the
 /// implementation solves the equivalent problem of finding the number of steps required to
 /// collapse the target molecule down to `e` using the inverse rules.
+#[autokey::tags("input-specific")]
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     let structure = build_structural_view(input.last().unwrap());
     let content_atoms = structure.chars().filter(|&c| c == '.').count();
@@ -31,7 +32,7 @@ fn parse_input<'a>(input: &[&'a str]) -> (Vec<(&'a str, &'a str)>, &'a str) {
         if line.contains("=>") {
             let mut split = line.split(" => ");
             result.push((split.next().unwrap(), split.next().unwrap()));
-        } else if line.len() > 0 {
+        } else if !line.is_empty() {
             return (result, line);
         }
     }
@@ -60,7 +61,7 @@ fn parse_input<'a>(input: &[&'a str]) -> (Vec<(&'a str, &'a str)>, &'a str) {
 /// If you closely examine the list of recipes, there's only a fixed number of expansion patterns
 /// that exist. These boil down into these categories:
 /// 
-/// ```
+/// ```text
 /// #1: 1 atom => 2 atoms
 /// #2: 1 atom => (1 atom) Rn (1 atom) Ar
 /// #3: 1 atom => (1 atom) Rn (1 atom) Y (1 atom) Ar
@@ -103,7 +104,7 @@ fn parse_input<'a>(input: &[&'a str]) -> (Vec<(&'a str, &'a str)>, &'a str) {
 /// steps required, and the required count starts at -1 to account for the fact that we need one
 /// fewer steps than content atoms to collapse them down to one.
 /// 
-/// ```
+/// ```text
 /// 3 . => +3
 /// 1 | => -1
 /// 
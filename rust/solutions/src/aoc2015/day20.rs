@@ -1,40 +1,42 @@
+use crate::util::num;
+use std::ops::Range;
+
 /// Find the earliest house that gets enough presents. In this incarnation, it's equivalent to
-/// finding the lowest number that has a high enough sum of proper factors.
+/// finding the lowest number that has a high enough sum of divisors.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    // I tried to do this a clever way via prime factorization or w/e, but this is fast enough for
-    // how trivial it is to implement.
-
-    let mut cache = vec![0; 1_000_000];
-    let target = input[0].parse::<i64>().unwrap();
-
-    for n in 1..cache.len() {
-        for d in 1.. {
-            if let Some(p) = cache.get_mut(n * d) {
-                *p += 10 * n as i64;
-            } else {
-                break;
-            }
-        }
-    }
-
-    Ok(cache.iter().enumerate().filter(|(_, &v)| v >= target).next().unwrap().0.to_string())
+    first_house_with(parse(input)?, 10, num::divisor_sums)
 }
 
 /// Find the earliest house that gets enough presents; but additional limitations no longer allow
 /// it to be abstracted to a nice mathematical problems.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    let mut cache = vec![0; 1_000_000];
-    let target = input[0].parse::<i64>().unwrap();
-    
-    for n in 1..cache.len() {
-        for d in 1..50 {
-            if let Some(p) = cache.get_mut(n * d) {
-                *p += 11 * n as i64;
-            } else {
-                break;
-            }
+    first_house_with(parse(input)?, 11, |limit| num::divisor_sums_bounded(limit, 50))
+}
+
+/// Finds the first house that gets at least `target` presents, with each elf delivering `factor`
+/// times their number. `presents` gives the sum of the numbers of the elves visiting each house in
+/// a range.
+fn first_house_with(
+    target: u64,
+    factor: u64,
+    presents: impl Fn(Range<usize>) -> Vec<u64>,
+) -> anyhow::Result<String> {
+    // House `n` gets at least `factor * n` presents from elf `n` alone, so that's an upper bound;
+    // but a loose one, so go through increasingly large ranges of houses instead.
+    let max = (target / factor) as usize + 1;
+    let (mut start, mut end) = (0, max.min(1 << 16));
+    loop {
+        if let Some(offset) = presents(start..end).iter().position(|&sum| sum * factor >= target) {
+            return Ok((start + offset).to_string());
         }
+        if end == max {
+            return Err(anyhow::anyhow!("no house gets enough presents"));
+        }
+        (start, end) = (end, max.min(end * 2));
     }
+}
 
-    Ok(cache.iter().enumerate().filter(|(_, &v)| v >= target).next().unwrap().0.to_string())
+/// Parses the puzzle input into the target number of presents.
+fn parse(input: &[&str]) -> anyhow::Result<u64> {
+    Ok(input.first().ok_or(anyhow::anyhow!("failed to parse input"))?.parse()?)
 }
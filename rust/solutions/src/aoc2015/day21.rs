@@ -3,9 +3,9 @@ use itertools::Itertools;
 /// Find the least gold you can spend to win against the boss.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     let boss = (
-        input[0].split(' ').last().unwrap().parse().unwrap(),
-        input[1].split(' ').last().unwrap().parse().unwrap(),
-        input[2].split(' ').last().unwrap().parse().unwrap());
+        input[0].split(' ').next_back().unwrap().parse().unwrap(),
+        input[1].split(' ').next_back().unwrap().parse().unwrap(),
+        input[2].split(' ').next_back().unwrap().parse().unwrap());
 
     Ok(all_loadouts()
         .map(resolve_loadout)
@@ -17,9 +17,9 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 /// Find the most gold you can spend and still lose against the boss.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     let boss = (
-        input[0].split(' ').last().unwrap().parse().unwrap(),
-        input[1].split(' ').last().unwrap().parse().unwrap(),
-        input[2].split(' ').last().unwrap().parse().unwrap());
+        input[0].split(' ').next_back().unwrap().parse().unwrap(),
+        input[1].split(' ').next_back().unwrap().parse().unwrap(),
+        input[2].split(' ').next_back().unwrap().parse().unwrap());
 
     Ok(all_loadouts()
         .map(resolve_loadout)
@@ -1,35 +1,24 @@
+use crate::scan;
+use crate::util::search;
+
 /// Find the cheapest possible win.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    Ok(least_mana_win(parse(input).ok_or(anyhow::anyhow!("failed to parse input"))?, false)
-        .to_string())
+    let state = parse(input)?;
+    Ok(least_mana_win(state, false).ok_or(anyhow::anyhow!("no way to win"))?.to_string())
 }
 
 /// Find the cheapest possible win in hard mode.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    Ok(least_mana_win(parse(input).ok_or(anyhow::anyhow!("failed to parse input"))?, true)
-        .to_string())
+    let state = parse(input)?;
+    Ok(least_mana_win(state, true).ok_or(anyhow::anyhow!("no way to win"))?.to_string())
 }
 
-/// Runs through all possible outcomes, and finds the cheapest win.
-fn least_mana_win(initial: State, hard: bool) -> usize {
-    let mut realities = vec![initial];
-    let mut best_win = usize::MAX;
-
-    while let Some(state) = realities.pop() {
-        // If we ALREADY have more mana spent than the best solution, no need to keep going.
-        if state.spent_mana > best_win {
-            continue;
-        }
-
-        // If we're a win, note that down and stop.
-        if state.enemy_health <= 0 {
-            best_win = best_win.min(state.spent_mana);
-            continue;
-        }
-
-        // Try casting each spell on a new timeline, and keep the ones that make sense (we didn't
-        // die or try casting illegally).
-        realities.extend(
+/// Finds the cheapest win, by searching through the possible battles with the mana spent on each
+/// turn as its cost.
+fn least_mana_win(initial: State, hard: bool) -> Option<usize> {
+    search::dijkstra(
+        initial,
+        |&state| {
             [
                 Spell::MagicMissile,
                 Spell::Drain,
@@ -38,17 +27,17 @@ fn least_mana_win(initial: State, hard: bool) -> usize {
                 Spell::Recharge,
             ]
             .into_iter()
-            .filter_map(|s| simulate_turn(state, s, hard))
-        );
-    }
-
-    best_win
+            .filter_map(move |s| simulate_turn(state, s, hard))
+        },
+        |state| state.enemy_health <= 0,
+    )
 }
 
 /// Simulates one full player turn and one full enemy turn, casting the provided `spell`. Returns
-/// `None` if the outcome is bad (we died or cast something illegal), otherwise `Some(new state)`.
-/// If `hard` is set, the player loses 1 HP at the beginning of each turn of their turns.
-fn simulate_turn(mut state: State, spell: Spell, hard: bool) -> Option<State> {
+/// `None` if the outcome is bad (we died or cast something illegal), otherwise `Some(new state)`
+/// along with the mana spent. If `hard` is set, the player loses 1 HP at the beginning of each of
+/// their turns.
+fn simulate_turn(mut state: State, spell: Spell, hard: bool) -> Option<(State, usize)> {
     // Player turn.
     if hard {
         state.health -= 1;
@@ -59,15 +48,15 @@ fn simulate_turn(mut state: State, spell: Spell, hard: bool) -> Option<State> {
 
     run_effects(&mut state);
     if state.enemy_health <= 0 {
-        return Some(state);
+        return Some((state, 0));
     }
 
+    let cost = spell.cost();
     let success = match spell {
-        Spell::MagicMissile => state.spend(53),
-        Spell::Drain => state.spend(73),
-        Spell::Shield => state.shield == 0 && state.spend(113),
-        Spell::Poison => state.poison == 0 && state.spend(173),
-        Spell::Recharge => state.recharge == 0 && state.spend(229),
+        Spell::MagicMissile | Spell::Drain => state.spend(cost),
+        Spell::Shield => state.shield == 0 && state.spend(cost),
+        Spell::Poison => state.poison == 0 && state.spend(cost),
+        Spell::Recharge => state.recharge == 0 && state.spend(cost),
     };
 
     if !success {
@@ -85,7 +74,7 @@ fn simulate_turn(mut state: State, spell: Spell, hard: bool) -> Option<State> {
     // Enemy turn.
     run_effects(&mut state);
     if state.enemy_health <= 0 {
-        return Some(state);
+        return Some((state, cost));
     }
 
     let damage = state.enemy_damage - if state.shield > 0 { 7 } else { 0 };
@@ -94,7 +83,7 @@ fn simulate_turn(mut state: State, spell: Spell, hard: bool) -> Option<State> {
         return None;
     }
 
-    Some(state)
+    Some((state, cost))
 }
 
 /// Runs one tick of each effect, applying their effects and reducing their duration.
@@ -121,8 +110,21 @@ enum Spell {
     Recharge,
 }
 
+impl Spell {
+    /// The amount of mana it takes to cast the spell.
+    fn cost(&self) -> usize {
+        match self {
+            Spell::MagicMissile => 53,
+            Spell::Drain => 73,
+            Spell::Shield => 113,
+            Spell::Poison => 173,
+            Spell::Recharge => 229,
+        }
+    }
+}
+
 /// A battle state.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
 struct State {
     health: i32,
     mana: usize,
@@ -131,7 +133,6 @@ struct State {
     poison: usize,
     shield: usize,
     recharge: usize,
-    spent_mana: usize,
 }
 
 impl State {
@@ -141,22 +142,23 @@ impl State {
             false
         } else {
             self.mana -= amount;
-            self.spent_mana += amount;
             true
         }
     }
 }
 
 /// Parses the puzzle input into a valid initial battle state.
-fn parse(input: &[&str]) -> Option<State> {
-    Some(State {
+fn parse(input: &[&str]) -> anyhow::Result<State> {
+    let (enemy_health,) = scan!(input.first().copied().unwrap_or_default(), "Hit Points: {}" => i32)?;
+    let (enemy_damage,) = scan!(input.get(1).copied().unwrap_or_default(), "Damage: {}" => i32)?;
+
+    Ok(State {
         health: 50,
         mana: 500,
-        enemy_health: input[0].split_once(": ")?.1.parse().ok()?,
-        enemy_damage: input[1].split_once(": ")?.1.parse().ok()?,
+        enemy_health,
+        enemy_damage,
         poison: 0,
         shield: 0,
         recharge: 0,
-        spent_mana: 0,
     })
 }
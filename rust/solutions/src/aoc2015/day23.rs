@@ -1,20 +1,21 @@
+use crate::scan;
+
 /// Run the program until completion, get `b`.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    Ok(simulate(input, 0, 0).to_string())
+    Ok(simulate(&parse(input)?, 0, 0).to_string())
 }
 
 /// With `a` set to 1 initially, run the program until completion, get `b`.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    Ok(simulate(input, 1, 0).to_string())
+    Ok(simulate(&parse(input)?, 1, 0).to_string())
 }
 
 /// Runs the `program` with initial `a` and `b`, and returns the value of `b` after completion.
-fn simulate(program: &[&str], mut a: usize, mut b: usize) -> usize {
+fn simulate(program: &[Instruction], mut a: usize, mut b: usize) -> usize {
     let mut ptr = 0usize;
-    while let Some(line) = program.get(ptr) {
-        let instruction = &line[0..3];
-        let register = if &line[4..5] == "a" { &mut a } else { &mut b };
-        match instruction {
+    while let Some(&Instruction { op, register, offset }) = program.get(ptr) {
+        let register = if register == "a" { &mut a } else { &mut b };
+        match op {
             "hlf" => { *register /= 2; ptr += 1 },
             "tpl" => { *register *= 3; ptr += 1 },
             "inc" => { *register += 1; ptr += 1 },
@@ -22,7 +23,6 @@ fn simulate(program: &[&str], mut a: usize, mut b: usize) -> usize {
                 || (jump == "jio" && *register == 1)
                 || (jump == "jie" && *register % 2 == 0) =>
             {
-                let offset = line.split(" ").last().unwrap().parse::<i32>().unwrap();
                 ptr = (ptr as i32 + offset) as usize;
             },
             _ => ptr += 1,
@@ -30,3 +30,32 @@ fn simulate(program: &[&str], mut a: usize, mut b: usize) -> usize {
     }
     b
 }
+
+/// Parses the puzzle input into a list of instructions.
+fn parse<'a>(input: &[&'a str]) -> anyhow::Result<Vec<Instruction<'a>>> {
+    input
+        .iter()
+        .map(|line| {
+            let (op, register, offset) = if line.starts_with("jmp") {
+                ("jmp", "a", scan!(line, "jmp {}" => i32)?.0)
+            } else if line.starts_with("ji") {
+                scan!(line, "{jie|jio} {a|b}, {}" => &str, &str, i32)?
+            } else {
+                let (op, register) = scan!(line, "{hlf|tpl|inc} {a|b}" => &str, &str)?;
+                (op, register, 0)
+            };
+            Ok(Instruction { op, register, offset })
+        })
+        .collect()
+}
+
+/// A single instruction of the program.
+#[derive(Copy, Clone)]
+struct Instruction<'a> {
+    /// The three-letter name of the instruction.
+    op: &'a str,
+    /// The register the instruction works on; meaningless for `jmp`.
+    register: &'a str,
+    /// How far to jump, if the instruction is a jump.
+    offset: i32,
+}
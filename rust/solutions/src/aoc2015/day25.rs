@@ -1,19 +1,15 @@
+use crate::util::num;
+
 /// Find the code to input from the infinite diagonal page.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     let index = to_index(parse(input).ok_or(anyhow::anyhow!("failed to parse input"))?);
 
-    let mut code = 20151125u64;
-    for _ in 1..index {
-        code = (code * 252533) % 33554393;
-    }
+    // Each code is the previous one times a constant, so the code at `index` is the first one
+    // times that constant to the power of `index - 1`.
+    let code = 20151125 * num::modpow(252533, index as u64 - 1, 33554393) % 33554393;
     Ok(code.to_string())
 }
 
-/// Freebie!
-pub fn part2(_input: &[&str]) -> anyhow::Result<String> {
-    Ok(format!("done!"))
-}
-
 /// Converts the row and column from the puzzle input into a straight index.
 /// # Formula
 /// `S(n)` = sum of numbers in `(1..=n)`, zero for n < 1.
@@ -47,7 +43,7 @@ fn to_index((row, col): (usize, usize)) -> usize {
 fn parse(s: &[&str]) -> Option<(usize, usize)> {
     let numbers: Vec<_> = s[0]
         .split(&[' ', '.', ','][..])
-        .filter(|s| s.len() > 0 && s.chars().all(|c| c.is_digit(10)))
+        .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()))
         .collect();
-    Some((numbers.get(0)?.parse().ok()?, numbers.get(1)?.parse().ok()?))
+    Some((numbers.first()?.parse().ok()?, numbers.get(1)?.parse().ok()?))
 }
@@ -1,45 +1,34 @@
-use std::collections::HashSet;
+use crate::util::plane::Plane;
+use crate::util::point::{Point2, Rotation2};
 
 /// Traverse the path from the instructions, return taxicab distance from origin afterwards.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    let (mut p, mut v) = ((0i32, 0i32), (0i32, -1i32));
+    let (mut p, mut v) = (Point2::ORIGIN, Point2::new(0, -1));
 
     for instruction in input[0].split(", ") {
-        v = match &instruction[0..1] {
-            "L" => rotated_left(v),
-            "R" => rotated_right(v),
-            _ => v,
-        };
+        v = v.rotate(turn(instruction));
         let d = instruction[1..].parse::<i32>()?;
-        p.0 += v.0 * d;
-        p.1 += v.1 * d;
+        p += v * d;
     }
 
-    Ok((p.0.abs() + p.1.abs()).to_string())
+    Ok(p.manhattan(Point2::ORIGIN).to_string())
 }
 
 /// Traverse the path from the instructions, remembering each point visited. Return taxicab distance
 /// from origin of the first point visited twice.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    let mut set = HashSet::new();
-    let (mut p, mut v) = ((0i32, 0i32), (0i32, -1i32));
+    let mut visited = Plane::new();
+    let (mut p, mut v) = (Point2::ORIGIN, Point2::new(0, -1));
 
     for instruction in input[0].split(", ") {
-        v = match &instruction[0..1] {
-            "L" => rotated_left(v),
-            "R" => rotated_right(v),
-            _ => v,
-        };
+        v = v.rotate(turn(instruction));
         let d = instruction[1..].parse::<i32>()?;
 
         for _ in 0..d {
-            p.0 += v.0;
-            p.1 += v.1;
+            p += v;
 
-            if set.contains(&p) {
-                return Ok((p.0.abs() + p.1.abs()).to_string());
-            } else {
-                set.insert(p);
+            if !visited.mark(p.into()) {
+                return Ok(p.manhattan(Point2::ORIGIN).to_string());
             }
         }
     }
@@ -47,8 +36,11 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     unreachable!()
 }
 
-/// Returns the same point rotated left by 90 degrees.
-fn rotated_left((x, y): (i32, i32)) -> (i32, i32) { (y, -x) }
-
-/// Returns the same point rotated right by 90 degrees.
-fn rotated_right((x, y): (i32, i32)) -> (i32, i32) { (-y, x) }
+/// Returns the turn made at the start of an instruction.
+fn turn(instruction: &str) -> Rotation2 {
+    match &instruction[0..1] {
+        "L" => Rotation2::LEFT,
+        "R" => Rotation2::RIGHT,
+        _ => Rotation2::IDENTITY,
+    }
+}
@@ -37,7 +37,7 @@ fn parse_vertical(input: &[&str]) -> Option<Vec<(u32, u32, u32)>> {
     let mut result = vec![];
     for chunk in input.chunks(3) {
         let mut items: Vec<_> = chunk
-            .into_iter()
+            .iter()
             .map(|s| s.split_whitespace().filter(|s| !s.is_empty()))
             .collect();
         for _ in 0..3 {
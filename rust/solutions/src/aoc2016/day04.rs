@@ -5,7 +5,7 @@ use itertools::Itertools;
 /// Sum the Ids of all real rooms. 
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     Ok(input
-        .into_iter()
+        .iter()
         .filter_map(|s| id_if_real(s))
         .sum::<u32>()
         .to_string())
@@ -14,7 +14,7 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 /// Find the Id of the North Pole storage room.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     Ok(input
-        .into_iter()
+        .iter()
         .filter_map(|s| id_if_northpole_object_storage(s))
         .next()
         .ok_or(anyhow::anyhow!("no room matched name"))?
@@ -40,7 +40,7 @@ fn id_if_real(roomspec: &str) -> Option<u32> {
         .collect();
 
     if top.len() == 5 && (0..5).all(|i| top[i] == checksum.as_bytes()[i]) {
-        Some(data.split('-').last()?.parse().ok()?)
+        Some(data.split('-').next_back()?.parse().ok()?)
     } else {
         None
     }
@@ -60,14 +60,14 @@ fn id_if_northpole_object_storage(roomspec: &str) -> Option<u32> {
 
     let name = "northpole object storage".as_bytes();
 
-    (name.len() < roomspec.as_bytes().len())
+    (name.len() < roomspec.len())
         .then(|| {
             id_if_real(roomspec).and_then(|id| {
                 let shift = (id % 26) as u8;
-                name.into_iter()
+                name.iter()
                     .zip(roomspec.as_bytes())
                     .all(|(&a, &b)| a == rotn(b, shift))
-                    .then(|| id)
+                    .then_some(id)
             })
         })
         .flatten()
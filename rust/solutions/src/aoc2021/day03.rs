@@ -30,7 +30,7 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
 /// determines which bit to keep (most or least common), and `tiebreaker` is the bit that gets used
 /// when both bits are equally common.
 fn find_rating(data: &[&str], partition: Ordering, tiebreaker: u8) -> u32 {
-    let mut candidates: Vec<_> = data.iter().map(|&s| s).collect();
+    let mut candidates: Vec<_> = data.to_vec();
 
     for i in 0..data[0].len() {
         // split the candidates list into those for which the relevant bit is '0', and for which
@@ -44,7 +44,7 @@ impl Bingo {
 
         for i in 0..boards.capacity() {
             boards[i].extend(input[(2 + i * 6)..(7 + i * 6)].iter()
-                .flat_map(|s| s.split(' ').filter_map(|t| (t.len() > 0).then(|| t.parse().ok()))));
+                .flat_map(|s| s.split(' ').filter(|t| !t.is_empty()).map(|t| t.parse().ok())));
         }
 
         Bingo {
@@ -56,14 +56,12 @@ impl Bingo {
     /// Draws a number, marks it off on all boards, and returns it. If there are no more numbers to
     /// be drawn, returns `None`.
     fn draw_number(&mut self) -> Option<u32> {
-        self.draws.pop_front().map(|number| {
+        self.draws.pop_front().inspect(|&number| {
             for value in self.boards.iter_mut().flat_map(|b| b.iter_mut()) {
                 if value.is_some() && number == value.unwrap() {
                     *value = None;
                 }
             }
-
-            number
         })
     }
 
@@ -74,11 +72,11 @@ impl Bingo {
 
             for d in 0..5 {
                 // horizontal
-                if [0 + 5 * d, 1 + 5 * d, 2 + 5 * d, 3 + 5 * d, 4 + 5 * d].iter().all(marked) {
+                if [5 * d, 1 + 5 * d, 2 + 5 * d, 3 + 5 * d, 4 + 5 * d].iter().all(marked) {
                     return Some(i);
                 }
                 // vertical
-                if [0 + d, 5 + d, 10 + d, 15 + d, 20 + d].iter().all(marked) {
+                if [d, 5 + d, 10 + d, 15 + d, 20 + d].iter().all(marked) {
                     return Some(i);
                 }
             }
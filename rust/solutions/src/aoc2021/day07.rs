@@ -16,6 +16,6 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     // The cheapest alignment in this case is the average (arithmetic mean). I'm not quite sure why,
     // though.
     let target: i32 = crabs.iter().copied().sum::<i32>() / crabs.len() as i32;
-    let sum = mapped_sum(&crabs, |i| ((target - i).abs() * ((target - i).abs() + 1) / 2)).unwrap();
+    let sum = mapped_sum(&crabs, |i| (target - i).abs() * ((target - i).abs() + 1) / 2).unwrap();
     Ok(sum.to_string())
 }
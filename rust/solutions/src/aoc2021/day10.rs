@@ -1,7 +1,7 @@
 /// Find the sum of all syntax error scores. That is, for every line with mismatched closing
 /// characters, count that mismatched closing character as an arbitrary score given by the puzzle.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    Ok(input.into_iter().fold(0, |a, b| {
+    Ok(input.iter().fold(0, |a, b| {
         a + match incomplete_tail(b) {
             Err(b'(') => 3,
             Err(b'[') => 57,
@@ -16,7 +16,7 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 /// closing characters, find those missing closing characters, then derive a score from them, then
 /// choose the median of the set (which is guaranteed to have an uneven number of entries).
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    let mut scores = input.into_iter().filter_map(|s| {
+    let mut scores = input.iter().filter_map(|s| {
         match incomplete_tail(s) {
             Ok(v) => Some(v.into_iter().rev().fold(0u64, |a, b| a * 5 + match b {
                 b'(' => 1,
@@ -41,10 +41,10 @@ fn incomplete_tail(s: &str) -> Result<Vec<u8>, u8> {
     for &byte in s.as_bytes() {
         match byte {
             b'(' | b'[' | b'{' | b'<' => stack.push(byte),
-            b')' => if stack.pop() != Some(b'(') { return Err(b'('); },
-            b']' => if stack.pop() != Some(b'[') { return Err(b'['); },
-            b'}' => if stack.pop() != Some(b'{') { return Err(b'{'); },
-            b'>' => if stack.pop() != Some(b'<') { return Err(b'<'); },
+            b')' if stack.pop() != Some(b'(') => return Err(b'('),
+            b']' if stack.pop() != Some(b'[') => return Err(b'['),
+            b'}' if stack.pop() != Some(b'{') => return Err(b'{'),
+            b'>' if stack.pop() != Some(b'<') => return Err(b'<'),
             _ => {},
         }
     }
@@ -1,11 +1,11 @@
-use itertools::Itertools;
-use std::collections::HashSet;
+use crate::util::automaton::{self, Automaton};
+use crate::util::grid::Grid;
 
 /// Simulate the octopi for 100 steps and count the total number of flashes.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
-    let mut map: Vec<Vec<u8>> = input.into_iter()
-        .map(|s| s.as_bytes().into_iter().map(|b| b - b'0').collect()).collect();
-    
+    let grid = parse(input).ok_or(anyhow::anyhow!("failed to parse input"))?;
+    let mut map = Automaton::new(grid, Octopus::default(), automaton::MOORE);
+
     let mut flashes = 0;
     for _ in 0..100 {
         flashes += step(&mut map);
@@ -15,11 +15,11 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 
 /// Find the number of steps needed until all octopi flash simultaneously.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
-    let mut map: Vec<Vec<u8>> = input.into_iter()
-        .map(|s| s.as_bytes().into_iter().map(|b| b - b'0').collect()).collect();
-    
+    let grid = parse(input).ok_or(anyhow::anyhow!("failed to parse input"))?;
+    let mut map = Automaton::new(grid, Octopus::default(), automaton::MOORE);
+
     for steps in 1.. {
-        if step(&mut map) == map.len() * map[0].len() {
+        if step(&mut map) == map.cells().count() {
             return Ok(steps.to_string());
         }
     }
@@ -28,45 +28,45 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
 }
 
 /// Steps the octopus simulation once and returns the number of flashes that occurred.
-fn step(map: &mut Vec<Vec<u8>>) -> usize {
-    let mut found = HashSet::new();
-    let mut queue = vec![];
-
-    for (y, x) in (0..map.len()).cartesian_product(0..map[0].len()) {
-        map[y][x] += 1;
-        if map[y][x] > 9 {
-            found.insert((x, y));
-            queue.push((x, y));
-        }
-    }
-
-    while let Some(p) = queue.pop() {
-        for (x, y) in neighbours(map[0].len(), map.len(), p) {
-            map[y][x] += 1;
-            if map[y][x] > 9 && !found.contains(&(x, y)) {
-                found.insert((x, y));
-                queue.push((x, y));
-            }
-        }
+fn step(map: &mut Automaton<Octopus>) -> usize {
+    for octopus in map.cells_mut() {
+        octopus.energy += 1;
     }
 
-    let flashes = found.len();
+    // Flashes cascade; each round, the octopi about to flash do so and give energy to their
+    // neighbours, until no new ones are about to flash.
+    map.step_until_stable(|_, &octopus, neighbours| Octopus {
+        energy: octopus.energy + neighbours.iter().filter(|n| n.about_to_flash()).count() as u8,
+        flashed: octopus.flashed || octopus.about_to_flash(),
+    });
 
-    for (x, y) in found {
-        map[y][x] = 0;
+    let mut flashes = 0;
+    for octopus in map.cells_mut() {
+        if octopus.flashed {
+            *octopus = Octopus::default();
+            flashes += 1;
+        }
     }
 
     flashes
 }
 
-/// Returns an iterator over the neighbours of coordinate. Works for invalid coordinates, but will
-/// most likely return nothing.
-fn neighbours(width: usize, height: usize, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
-    [(-1i32, 0), (0, -1i32), (1, 0), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)].iter()
-        .filter_map(move |(dx, dy)| -> Option<(usize, usize)> {
-            let x = usize::try_from(x as i32 + dx).ok()?;
-            let y = usize::try_from(y as i32 + dy).ok()?;
+/// An octopus, as far as the simulation is concerned.
+#[derive(Copy, Clone, Default, PartialEq)]
+struct Octopus {
+    energy: u8,
+    /// Whether the octopus has flashed during the current step already.
+    flashed: bool,
+}
+
+impl Octopus {
+    /// Whether the octopus has enough energy to flash, but hasn't yet.
+    fn about_to_flash(&self) -> bool {
+        self.energy > 9 && !self.flashed
+    }
+}
 
-            (x < width && y < height).then(|| (x, y))
-        })
+/// Parses the puzzle input into a map of octopi.
+fn parse(input: &[&str]) -> Option<Grid<Octopus>> {
+    Grid::parse(input, |b| b.is_ascii_digit().then(|| Octopus { energy: b - b'0', flashed: false }))
 }
@@ -20,7 +20,7 @@ struct Graph<'a> {
 impl<'a> Graph<'a> {
     /// Parses the puzzle input into a graph.
     fn from_input(input: &[&'a str]) -> Graph<'a> {
-        fn index<T: PartialEq>(v: &Vec<T>, item: &T) -> usize {
+        fn index<T: PartialEq>(v: &[T], item: &T) -> usize {
             v.iter().position(|t| t == item).unwrap()
         }
 
@@ -58,7 +58,7 @@ impl<'a> Graph<'a> {
                     _ if target == g.end => 1,
                     // Small, open cave. Have to copy the "open" table, with the corresponding entry
                     // set to false.
-                    (true, _) if (b'a'..=b'z').contains(&g.nodes[target].as_bytes()[0]) =>
+                    (true, _) if g.nodes[target].as_bytes()[0].is_ascii_lowercase() =>
                         sub_paths(g, target, open_mask & !(1 << target), grace),
                     // Big, open cave. No need to modify state.
                     (true, _) => sub_paths(g, target, open_mask, grace),
@@ -14,9 +14,21 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     Ok(score_after_steps(polymer, &pairs, 40).to_string())
 }
 
+/// Same as [`part1`], but using the old recursive approach.
+pub fn part1_recursive(input: &[&str]) -> anyhow::Result<String> {
+    let (polymer, pairs) = parse(input).ok_or(anyhow::anyhow!("failed to parse input"))?;
+    Ok(score_after_steps_recursive(polymer, &pairs, 10).to_string())
+}
+
+/// Same as [`part2`], but using the old recursive approach.
+pub fn part2_recursive(input: &[&str]) -> anyhow::Result<String> {
+    let (polymer, pairs) = parse(input).ok_or(anyhow::anyhow!("failed to parse input"))?;
+    Ok(score_after_steps_recursive(polymer, &pairs, 40).to_string())
+}
+
 /// Expands the `polymer` by the given amount of `steps`, using `rules`; then returns the difference
 /// in quantity of the most common byte and the least common byte in the output.
-fn score_after_steps(polymer: &[u8], rules: &HashMap<(u8, u8), u8>, steps: usize) -> usize {
+fn score_after_steps(polymer: &[u8], rules: &Rules, steps: usize) -> usize {
     let mut counts = HashMap::new();
     let mut pairs = HashMap::new();
 
@@ -51,12 +63,17 @@ fn score_after_steps(polymer: &[u8], rules: &HashMap<(u8, u8), u8>, steps: usize
 
 /// Expands the `polymer` by the given amount of `steps`, using `rules`; then returns the difference
 /// in quantity of the most common byte and the least common byte in the output.
-#[allow(dead_code)]
-fn score_after_steps_old(polymer: &[u8], rules: &HashMap<(u8, u8), u8>, steps: usize) -> usize {
+///
+/// This is the old approach, which recursively expands each pair and memoizes the counts of the
+/// resulting subtrees.
+fn score_after_steps_recursive(polymer: &[u8], rules: &Rules, steps: usize) -> usize {
+    // Maps a pair and a remaining step count to the counts of bytes spawned by fully expanding it.
+    type Cache = HashMap<((u8, u8), usize), HashMap<u8, usize>>;
+
     // Counts the characters resulting from expanding one `pair`, `steps` amount of times. The
     // result is stored in `counts`. This is hugely recursive, and results for computed subtrees
     // are memoized into `cache`.
-    fn recurse(steps: usize, pair: (u8, u8), rules: &HashMap<(u8, u8), u8>, counts: &mut HashMap<u8, usize>, cache: &mut HashMap<((u8, u8), usize), HashMap<u8, usize>>) {
+    fn recurse(steps: usize, pair: (u8, u8), rules: &Rules, counts: &mut HashMap<u8, usize>, cache: &mut Cache) {
         // Recursion base case check.
         if steps > 0 {
             // As a result of this call, one new byte is spawned. Count it.
@@ -81,7 +98,7 @@ fn score_after_steps_old(polymer: &[u8], rules: &HashMap<(u8, u8), u8>, steps: u
         }
     }
 
-    let mut cache: HashMap<((u8, u8), usize), HashMap<u8, usize>> = HashMap::new();
+    let mut cache: Cache = HashMap::new();
     let mut counts = HashMap::new();
     // Count the actual bytes in the input polymer.
     for &c in polymer {
@@ -101,9 +118,12 @@ fn score_after_steps_old(polymer: &[u8], rules: &HashMap<(u8, u8), u8>, steps: u
     counts.values().max().unwrap() - counts.values().min().unwrap()
 }
 
+/// Maps pairs of bytes to the byte that gets inserted between them.
+type Rules = HashMap<(u8, u8), u8>;
+
 /// Parses the puzzle input into an input polymer and an expansion rule map.
-fn parse<'a>(input: &[&'a str]) -> Option<(&'a [u8], HashMap<(u8, u8), u8>)> {
-    let mut lines = input.into_iter();
+fn parse<'a>(input: &[&'a str]) -> Option<(&'a [u8], Rules)> {
+    let mut lines = input.iter();
     let polymer = lines.next().unwrap().as_bytes();
     let mut pairs = HashMap::new();
 
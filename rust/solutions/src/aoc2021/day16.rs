@@ -130,8 +130,8 @@ fn to_bits_string(s: &[&str]) -> String {
     let mut bits = String::with_capacity(s[0].len() * 4);
     for &c in s[0].as_bytes() {
         bits.push_str(&match c {
-            c if b'0' <= c && c <= b'9' => format!("{:04b}", c - b'0'),
-            c if b'A' <= c && c <= b'F' => format!("{:04b}", 10 + c - b'A'),
+            c if c.is_ascii_digit() => format!("{:04b}", c - b'0'),
+            c if (b'A'..=b'F').contains(&c) => format!("{:04b}", 10 + c - b'A'),
             _ => String::new(),
         })
     }
@@ -1,7 +1,9 @@
+pub mod flat;
+
 /// Sum up all the snailfish numbers in the input, find the magnitude of the result.
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     let mut expressions = input
-        .into_iter()
+        .iter()
         .copied()
         .map(Expr::from_input)
         .collect::<Vec<_>>();
@@ -16,7 +18,7 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 /// input.
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     let expressions = input
-        .into_iter()
+        .iter()
         .copied()
         .map(Expr::from_input)
         .collect::<Vec<_>>();
@@ -201,13 +203,11 @@ impl Expr {
                     stack.push(2 * i + 2);
                     stack.push(2 * i + 1);
                 }
-                Node::Value(n) => {
-                    if n >= 10 {
-                        self.nodes[i] = Node::Pair;
-                        self.nodes[2 * i + 1] = Node::Value(n / 2);
-                        self.nodes[2 * i + 2] = Node::Value((n + 1) / 2);
-                        return true;
-                    }
+                Node::Value(n) if n >= 10 => {
+                    self.nodes[i] = Node::Pair;
+                    self.nodes[2 * i + 1] = Node::Value(n / 2);
+                    self.nodes[2 * i + 2] = Node::Value(n.div_ceil(2));
+                    return true;
                 }
                 _ => {}
             }
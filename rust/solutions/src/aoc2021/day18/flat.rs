@@ -1,4 +1,8 @@
+//! The first attempt at this day, which keeps snailfish numbers as a flat sequence of tokens
+//! instead of a tree. Kept around for comparison.
+
 /// Sum up all the snailfish numbers in the input, find the magnitude of the result.
+#[autokey::aoc(2021, 18, 1, flat)]
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     let expressions = parse(input);
     let final_expression = expressions.into_iter().reduce(add_expressions);
@@ -8,6 +12,7 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 
 /// Find the highest magnitude obtainable from adding any two different snailfish numbers from the
 /// input.
+#[autokey::aoc(2021, 18, 2, flat)]
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     let expressions = parse(input);
 
@@ -48,8 +53,8 @@ impl Atom {
 fn magnitude(atoms: &[Atom]) -> u64 {
     fn closing_index(atoms: &[Atom], start: usize) -> usize {
         let mut depth = 0;
-        for i in start + 1.. {
-            match atoms[i] {
+        for (i, atom) in atoms.iter().enumerate().skip(start + 1) {
+            match atom {
                 Atom::OpenPair => depth += 1,
                 Atom::ClosePair if depth == 0 => return i,
                 Atom::ClosePair => depth -= 1,
@@ -147,7 +152,7 @@ fn split_once(atoms: &mut Vec<Atom>) -> bool {
             if n >= 10 {
                 atoms[i] = Atom::OpenPair;
                 atoms.insert(i + 1, Atom::Literal(n / 2));
-                atoms.insert(i + 2, Atom::Literal((n + 1) / 2));
+                atoms.insert(i + 2, Atom::Literal(n.div_ceil(2)));
                 atoms.insert(i + 3, Atom::ClosePair);
                 return true;
             }
@@ -1,6 +1,10 @@
+//! The first attempt at this day, which stores lit pixels in a [`HashSet`] instead of using an
+//! [`Automaton`](crate::util::automaton::Automaton). Kept around for comparison.
+
 use std::collections::HashSet;
 
 /// Count the number of lit pixels after 2 iterations of the enhancing algorithm.
+#[autokey::aoc(2021, 20, 1, sparse)]
 pub fn part1(input: &[&str]) -> anyhow::Result<String> {
     let mut image = Image::from_input(&input[2..]).unwrap();
     image.enhance(input[0].as_bytes());
@@ -9,6 +13,7 @@ pub fn part1(input: &[&str]) -> anyhow::Result<String> {
 }
 
 /// Count the number of lit pixels after 50 iterations of the enhancing algorithm.
+#[autokey::aoc(2021, 20, 2, sparse)]
 pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     let mut image = Image::from_input(&input[2..]).unwrap();
     for _ in 0..50 {
@@ -40,7 +40,7 @@ pub fn part2(input: &[&str]) -> anyhow::Result<String> {
     )]);
 
     let (mut w1, mut w2) = (0, 0);
-    while universes.len() > 0 {
+    while !universes.is_empty() {
         let key = *universes.keys().next().unwrap();
         let (state, times) = universes.remove_entry(&key).unwrap();
         let (subw1, subw2, new) = split_roll(state);
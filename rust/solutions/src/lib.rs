@@ -1,6 +1,7 @@
 //! The crate of solutions.
 
-mod util;
+// Public, since which utilities get used depends on which events are compiled.
+pub mod util;
 
 // Automatically generates all the relevant 'mod' directives for all solution files that exist.
 // A solution file is one that is in /src/aoc{year}/day{2-digit number}.rs
@@ -1,3 +1,14 @@
+pub mod automaton;
+pub mod combinatorics;
+pub mod grid;
+pub mod interval;
+pub mod num;
+pub mod plane;
+pub mod point;
+pub mod scan;
+pub mod search;
+pub mod tour;
+
 /// Reorders the elements in `slice` such that all elements for which the result of calling `f`
 /// matches `item` are in the beginning of the slice, followed by all elements for which it doesn't
 /// match. No other guarantees about the order of the elements is made. Returns the amount of
@@ -24,7 +35,7 @@ where
     N: std::ops::Add<Output = N>,
     F: FnMut(&T) -> N,
 {
-    let mut items = items.into_iter();
+    let mut items = items.iter();
     let mut result = items.next().map(&mut f)?;
     for item in items {
         result = result + f(item);
@@ -0,0 +1,383 @@
+//! A cellular automaton engine: a board of cells that all change at once each step, according to
+//! a rule that looks at a cell and its neighbours.
+//!
+//! Boards are either bounded, where everything outside is a fixed background that never changes,
+//! or unbounded, where the board is infinite and everything outside of the part that's actually
+//! stored is a background that evolves according to the same rule as everything else.
+
+use super::grid::Grid;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The 8 surrounding cells.
+pub const MOORE: &[(i32, i32)] = &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// The 4 orthogonally adjacent cells.
+pub const VON_NEUMANN: &[(i32, i32)] = &[(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The 3x3 square around a cell, including the cell itself, row by row.
+pub const SQUARE: &[(i32, i32)] = &[
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (0, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1),
+];
+
+/// A cellular automaton. Cells are addressed by `(x, y)` positions on the board, with the top left
+/// corner of the initial cells at `(0, 0)`; on unbounded boards, positions can go negative as the
+/// board grows.
+#[derive(Clone, Debug)]
+pub struct Automaton<T> {
+    /// The stored part of the board. Only the cells in `area` count; everything else is
+    /// background, whatever happens to be stored there.
+    cells: Grid<T>,
+    /// Where the next generation is written to, so the current one stays intact while stepping.
+    /// Always of the same size as `cells`, so the two can just be swapped afterwards.
+    buffer: Grid<T>,
+    /// The board position of the top left cell in `cells`.
+    origin: (i32, i32),
+    /// The part of `cells` that isn't just background. On unbounded boards, there's room left
+    /// around it to grow into, so the storage doesn't need to be reallocated every step.
+    area: Area,
+    /// The value of every cell outside of `area`.
+    background: T,
+    /// Offsets to the cells that count as neighbours of a cell.
+    neighbourhood: Vec<(i32, i32)>,
+    /// How far the neighbourhood reaches in any direction.
+    radius: i32,
+    /// Whether the board is infinite, rather than ending at the edges of the initial cells.
+    unbounded: bool,
+}
+
+/// A rectangle of cells in a grid, given by its top left corner and its size.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+struct Area {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Area {
+    /// The area, extended by `by` cells in each direction.
+    fn grow(self, by: usize) -> Area {
+        Area {
+            x: self.x - by,
+            y: self.y - by,
+            width: self.width + 2 * by,
+            height: self.height + 2 * by,
+        }
+    }
+
+    /// All positions in the area, row by row.
+    fn positions(self) -> impl Iterator<Item = (usize, usize)> {
+        let xs = self.x..self.x + self.width;
+        (self.y..self.y + self.height).flat_map(move |y| xs.clone().map(move |x| (x, y)))
+    }
+}
+
+impl<T: Clone + PartialEq> Automaton<T> {
+    /// Creates a bounded automaton with the given cells. Everything outside is `background`, which
+    /// never changes.
+    pub fn new(cells: Grid<T>, background: T, neighbourhood: &[(i32, i32)]) -> Self {
+        let radius =
+            neighbourhood.iter().map(|&(dx, dy)| dx.abs().max(dy.abs())).max().unwrap_or(0);
+        Automaton {
+            area: Area { x: 0, y: 0, width: cells.width(), height: cells.height() },
+            buffer: cells.clone(),
+            cells,
+            origin: (0, 0),
+            background,
+            neighbourhood: neighbourhood.to_vec(),
+            radius,
+            unbounded: false,
+        }
+    }
+
+    /// Creates an unbounded automaton with the given cells, surrounded by an infinite amount of
+    /// `background` in all directions.
+    ///
+    /// The background is stepped along with everything else, by applying the rule to a position
+    /// outside of the stored cells. Thus rules shouldn't depend on the position when far away from
+    /// any interesting cells.
+    pub fn unbounded(cells: Grid<T>, background: T, neighbourhood: &[(i32, i32)]) -> Self {
+        let mut automaton =
+            Automaton { unbounded: true, ..Self::new(cells, background, neighbourhood) };
+        automaton.trim();
+        automaton
+    }
+
+    /// The cells that aren't just background, row by row. For bounded automata, that's the whole
+    /// board.
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        let area = self.area;
+        self.cells
+            .rows()
+            .skip(area.y)
+            .take(area.height)
+            .flat_map(move |row| &row[area.x..area.x + area.width])
+    }
+
+    /// The cells that aren't just background, mutably, to change them between steps.
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let area = self.area;
+        self.cells
+            .rows_mut()
+            .skip(area.y)
+            .take(area.height)
+            .flat_map(move |row| &mut row[area.x..area.x + area.width])
+    }
+
+    /// The value of all cells outside of [`cells`](Automaton::cells).
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    /// The board position of the top left cell of [`cells`](Automaton::cells), and their width
+    /// and height.
+    pub fn bounds(&self) -> ((i32, i32), (usize, usize)) {
+        let Area { x, y, width, height } = self.area;
+        ((self.origin.0 + x as i32, self.origin.1 + y as i32), (width, height))
+    }
+
+    /// Gets the cell at the board position `p`.
+    pub fn get(&self, (x, y): (i32, i32)) -> &T {
+        // Positions left of or above the area wrap around to huge numbers, so they fail the size
+        // checks just like ones past the right or bottom edge.
+        let Area { x: x0, y: y0, width, height } = self.area;
+        let dx = ((x - self.origin.0) as usize).wrapping_sub(x0);
+        let dy = ((y - self.origin.1) as usize).wrapping_sub(y0);
+        if dx >= width || dy >= height {
+            return &self.background;
+        }
+        // The area is always inside the stored cells, so indexing the grid would only check that
+        // again, which is measurably slow in the neighbour lookups of `step`.
+        &self.cells.as_slice()[(y0 + dy) * self.cells.width() + x0 + dx]
+    }
+
+    /// Advances the automaton by one step. `rule` gets the position of a cell, its value and the
+    /// values of its neighbours (in the order of the neighbourhood), and returns its new value.
+    /// Returns whether anything changed.
+    pub fn step(&mut self, mut rule: impl FnMut((i32, i32), &T, &[T]) -> T) -> bool {
+        // An unbounded board grows by the radius of the neighbourhood in each direction, since
+        // that's how far changes can spread.
+        let grow = if self.unbounded { self.radius as usize } else { 0 };
+        self.reserve(grow);
+        let area = self.area.grow(grow);
+
+        let mut neighbours = Vec::with_capacity(self.neighbourhood.len());
+        let background = if self.unbounded {
+            neighbours.resize(self.neighbourhood.len(), self.background.clone());
+            let far_away = (self.origin.0 - self.radius - 1, self.origin.1 - self.radius - 1);
+            rule(far_away, &self.background, &neighbours)
+        } else {
+            self.background.clone()
+        };
+
+        // Everything outside of the new area is background, both before and after; so checking
+        // the cells in it is enough to tell whether anything changed.
+        let mut changed = background != self.background;
+        for (x, y) in area.positions() {
+            let p = (self.origin.0 + x as i32, self.origin.1 + y as i32);
+            neighbours.clear();
+            neighbours.extend(
+                self.neighbourhood.iter().map(|&(dx, dy)| self.get((p.0 + dx, p.1 + dy)).clone()),
+            );
+            let old = self.get(p);
+            let cell = rule(p, old, &neighbours);
+            changed = changed || cell != *old;
+            self.buffer[(x, y)] = cell;
+        }
+
+        std::mem::swap(&mut self.cells, &mut self.buffer);
+        self.background = background;
+        self.area = area;
+        if self.unbounded {
+            self.trim();
+        }
+
+        changed
+    }
+
+    /// Steps the automaton until a step doesn't change anything anymore, and returns the number of
+    /// steps that took, including that last one.
+    pub fn step_until_stable(&mut self, mut rule: impl FnMut((i32, i32), &T, &[T]) -> T) -> usize {
+        let mut steps = 1;
+        while self.step(&mut rule) {
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Steps the automaton until it reaches a state it was already in. Returns the number of steps
+    /// until the first state that repeats, and the length of the cycle.
+    pub fn find_cycle(&mut self, mut rule: impl FnMut((i32, i32), &T, &[T]) -> T) -> (usize, usize)
+    where
+        T: Hash + Eq,
+    {
+        let mut seen = HashMap::new();
+        for steps in 0.. {
+            let cells: Vec<_> = self.cells().cloned().collect();
+            let state = (self.bounds(), self.background.clone(), cells);
+            if let Some(start) = seen.insert(state, steps) {
+                return (start, steps - start);
+            }
+            self.step(&mut rule);
+        }
+        unreachable!()
+    }
+
+    /// Makes sure there's room for `area` to grow by `by` cells in each direction, moving the
+    /// cells into larger storage if there isn't.
+    fn reserve(&mut self, by: usize) {
+        let Area { x, y, width, height } = self.area;
+        let fits_x = x >= by && x + width + by <= self.cells.width();
+        let fits_y = y >= by && y + height + by <= self.cells.height();
+        if fits_x && fits_y {
+            return;
+        }
+
+        // Leave room for a while, so that growing steadily only needs a few reallocations.
+        let margin = by + width.max(height) / 2;
+        let cells = Grid::from_fn(width + 2 * margin, height + 2 * margin, |(cx, cy)| {
+            if (margin..margin + width).contains(&cx) && (margin..margin + height).contains(&cy) {
+                self.cells[(cx - margin + x, cy - margin + y)].clone()
+            } else {
+                self.background.clone()
+            }
+        });
+
+        self.origin.0 += x as i32 - margin as i32;
+        self.origin.1 += y as i32 - margin as i32;
+        self.area = Area { x: margin, y: margin, width, height };
+        self.buffer = cells.clone();
+        self.cells = cells;
+    }
+
+    /// Shrinks the area of an unbounded board until there are no rows or columns left on its edges
+    /// that are only background.
+    fn trim(&mut self) {
+        let is_background = |cell: &T| *cell == self.background;
+        let Area { x, y, width, height } = self.area;
+        let empty_row = |ry: usize| self.cells.row(ry)[x..x + width].iter().all(is_background);
+        let empty_column =
+            |cx: usize| (y..y + height).all(|cy| is_background(&self.cells[(cx, cy)]));
+
+        let top = (y..y + height).take_while(|&ry| empty_row(ry)).count();
+        if top == height {
+            self.area = Area { x, y, width: 0, height: 0 };
+            return;
+        }
+        let bottom = (y..y + height).rev().take_while(|&ry| empty_row(ry)).count();
+        let left = (x..x + width).take_while(|&cx| empty_column(cx)).count();
+        let right = (x..x + width).rev().take_while(|&cx| empty_column(cx)).count();
+
+        self.area = Area {
+            x: x + left,
+            y: y + top,
+            width: width - left - right,
+            height: height - top - bottom,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a picture of live (`#`) and dead (`.`) cells.
+    fn board(rows: &[&str]) -> Grid<bool> {
+        Grid::parse(rows, |b| Some(b == b'#')).unwrap()
+    }
+
+    /// Conway's Game of Life.
+    fn life(_: (i32, i32), &alive: &bool, neighbours: &[bool]) -> bool {
+        let n = neighbours.iter().filter(|&&b| b).count();
+        (alive && n == 2) || n == 3
+    }
+
+    /// The positions of all live cells.
+    fn alive(automaton: &Automaton<bool>) -> Vec<(i32, i32)> {
+        let ((x0, y0), (width, height)) = automaton.bounds();
+        let (xs, ys) = (x0..x0 + width as i32, y0..y0 + height as i32);
+        let positions = ys.flat_map(|y| xs.clone().map(move |x| (x, y)));
+        positions.filter(|&p| *automaton.get(p)).collect()
+    }
+
+    #[test]
+    fn bounded_edges_see_background() {
+        // In the corner, the block is missing the neighbours it needs to stay alive.
+        let mut automaton = Automaton::new(board(&["#.", ".."]), false, MOORE);
+        assert!(automaton.step(life));
+        assert!(!automaton.step(life));
+        assert_eq!(automaton.cells().filter(|&&b| b).count(), 0);
+        assert_eq!(automaton.bounds(), ((0, 0), (2, 2)));
+    }
+
+    #[test]
+    fn blinker_cycle() {
+        let blinker = board(&[".....", "..#..", "..#..", "..#..", "....."]);
+        let mut automaton = Automaton::new(blinker, false, MOORE);
+        assert_eq!(automaton.find_cycle(life), (0, 2));
+    }
+
+    #[test]
+    fn stable_block() {
+        let mut automaton = Automaton::unbounded(board(&["##", "##"]), false, MOORE);
+        assert_eq!(automaton.step_until_stable(life), 1);
+        assert_eq!(alive(&automaton), vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn unbounded_trims_and_grows() {
+        let glider = board(&[".....", "..#..", "...#.", ".###.", "....."]);
+        let mut automaton = Automaton::unbounded(glider, false, MOORE);
+        assert_eq!(automaton.bounds(), ((1, 1), (3, 3)));
+
+        // Every 4 steps, a glider moves one cell diagonally; far enough that the storage needs to
+        // grow a few times along the way.
+        let start = alive(&automaton);
+        for _ in 0..100 {
+            automaton.step(life);
+        }
+        assert_eq!(automaton.bounds(), ((26, 26), (3, 3)));
+        let moved: Vec<_> = start.iter().map(|&(x, y)| (x + 25, y + 25)).collect();
+        assert_eq!(alive(&automaton), moved);
+        assert!(!automaton.get((1, 1)));
+    }
+
+    #[test]
+    fn unbounded_dies_out() {
+        let mut automaton = Automaton::unbounded(board(&["#"]), false, MOORE);
+        assert!(automaton.step(life));
+        assert_eq!(automaton.bounds().1, (0, 0));
+        assert_eq!(automaton.cells().count(), 0);
+        assert!(!automaton.step(life));
+    }
+
+    #[test]
+    fn background_evolves() {
+        // Every cell flips, including the infinitely many background ones.
+        let flip = |_: (i32, i32), &cell: &bool, _: &[bool]| !cell;
+        let mut automaton = Automaton::unbounded(board(&["#."]), false, SQUARE);
+        assert_eq!(automaton.bounds(), ((0, 0), (1, 1)));
+
+        assert!(automaton.step(flip));
+        assert!(*automaton.background());
+        assert!(!automaton.get((0, 0)));
+        assert!(automaton.get((1, 0)));
+        assert!(automaton.get((-50, 70)));
+        assert_eq!(automaton.find_cycle(flip), (0, 2));
+    }
+
+    #[test]
+    fn cells_mut_changes_the_board() {
+        let mut automaton = Automaton::new(Grid::new(2, 2, 0u8), 0, VON_NEUMANN);
+        for (i, cell) in automaton.cells_mut().enumerate() {
+            *cell = i as u8;
+        }
+        assert_eq!(*automaton.get((1, 1)), 3);
+        assert_eq!(*automaton.get((2, 1)), 0);
+        automaton.step(|_, &cell, neighbours| cell + neighbours.iter().sum::<u8>());
+        assert_eq!(automaton.cells().copied().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    }
+}
@@ -0,0 +1,246 @@
+//! Choosing things: subsets, sums of subsets, and ways of splitting numbers up.
+
+/// All subsets of `items`, starting with the empty one. There's no limit on the number of items,
+/// but keep in mind that there's `2^n` subsets of `n` items.
+pub fn subsets<T>(items: &[T]) -> Subsets<'_, T> {
+    Subsets { items, chosen: Some(vec![false; items.len()]) }
+}
+
+/// All subsets of `items` with exactly `size` elements, in lexicographic order of their indices.
+pub fn subsets_of_size<T>(items: &[T], size: usize) -> SubsetsOfSize<'_, T> {
+    SubsetsOfSize { items, indices: (size <= items.len()).then(|| (0..size).collect()) }
+}
+
+/// Counts the subsets of `items` that sum up to exactly `target`, by their size; that is, entry
+/// `k` of the result is the number of such subsets with `k` items.
+pub fn subset_sum_counts(items: &[usize], target: usize) -> Vec<u64> {
+    // counts[k][s] is the number of subsets of the items seen so far with k items summing to s.
+    let mut counts = vec![vec![0u64; target + 1]; items.len() + 1];
+    counts[0][0] = 1;
+
+    for (seen, &item) in items.iter().enumerate() {
+        // Going downwards, so that each item is only used once.
+        for k in (1..=seen + 1).rev() {
+            for s in (item..=target).rev() {
+                counts[k][s] += counts[k - 1][s - item];
+            }
+        }
+    }
+
+    counts.into_iter().map(|row| row[target]).collect()
+}
+
+/// Whether `items` can be split into `k` groups that all have the same sum.
+pub fn partitions_evenly(items: &[usize], k: usize) -> bool {
+    if k == 0 {
+        return items.is_empty();
+    }
+
+    let total: usize = items.iter().sum();
+    if !total.is_multiple_of(k) {
+        return false;
+    }
+
+    // Placing the largest items first makes dead ends show up a lot sooner.
+    let mut items = items.to_vec();
+    items.sort_unstable_by(|a, b| b.cmp(a));
+    let mut groups = vec![0; k];
+    fill_groups(&items, &mut groups, total / k)
+}
+
+/// Tries to place all `items` into `groups` (given by their sums so far) without any going over
+/// `target`. Used by [`partitions_evenly`].
+fn fill_groups(items: &[usize], groups: &mut [usize], target: usize) -> bool {
+    let Some((&item, rest)) = items.split_first() else {
+        return true;
+    };
+
+    for i in 0..groups.len() {
+        // Putting the item into a group with the same sum as one already tried would lead to the
+        // exact same situation.
+        if groups[i] + item > target || groups[..i].contains(&groups[i]) {
+            continue;
+        }
+
+        groups[i] += item;
+        let found = fill_groups(rest, groups, target);
+        groups[i] -= item;
+        if found {
+            return true;
+        }
+    }
+    false
+}
+
+/// All ways of writing `n` as the sum of exactly `k` numbers (including zeroes), where the order
+/// matters; in lexicographic order.
+pub fn compositions(n: usize, k: usize) -> Compositions {
+    let parts = match k {
+        0 => (n == 0).then(Vec::new),
+        _ => {
+            let mut parts = vec![0; k];
+            parts[k - 1] = n;
+            Some(parts)
+        }
+    };
+    Compositions { parts, started: false }
+}
+
+/// Iterator returned by [`subsets`].
+pub struct Subsets<'a, T> {
+    items: &'a [T],
+    /// Which items are in the next subset; `None` once done.
+    chosen: Option<Vec<bool>>,
+}
+
+impl<'a, T> Iterator for Subsets<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chosen = self.chosen.as_mut()?;
+        let subset = self.items.iter().zip(chosen.iter()).filter(|(_, &c)| c).map(|(item, _)| item);
+        let subset = subset.collect();
+
+        // Counts up in binary, with the first item as the least significant digit.
+        match chosen.iter().position(|&c| !c) {
+            Some(i) => {
+                chosen[..i].fill(false);
+                chosen[i] = true;
+            }
+            None => self.chosen = None,
+        }
+
+        Some(subset)
+    }
+}
+
+/// Iterator returned by [`subsets_of_size`].
+pub struct SubsetsOfSize<'a, T> {
+    items: &'a [T],
+    /// The indices of the items in the next subset, in ascending order; `None` once done.
+    indices: Option<Vec<usize>>,
+}
+
+impl<'a, T> Iterator for SubsetsOfSize<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.indices.as_mut()?;
+        let subset = indices.iter().map(|&i| &self.items[i]).collect();
+
+        // Move the last index that still can up by one, and all indices after it right behind it.
+        let (n, size) = (self.items.len(), indices.len());
+        match (0..size).rev().find(|&i| indices[i] < n - size + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..size {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.indices = None,
+        }
+
+        Some(subset)
+    }
+}
+
+/// Iterator returned by [`compositions`].
+pub struct Compositions {
+    /// The last composition handed out, or the first one if `started` isn't set yet; `None` once
+    /// done.
+    parts: Option<Vec<usize>>,
+    started: bool,
+}
+
+impl Compositions {
+    /// Like [`Iterator::next`], but lends out the composition instead of allocating a new one each
+    /// time, which adds up when going through hundreds of thousands of them.
+    pub fn next_slice(&mut self) -> Option<&[usize]> {
+        if !std::mem::replace(&mut self.started, true) {
+            return self.parts.as_deref();
+        }
+
+        // Take the last non-zero part (apart from the first one), move one from it into the part
+        // before it, and the rest all the way to the end.
+        let parts = self.parts.as_mut()?;
+        let k = parts.len();
+        match (1..k).rev().find(|&i| parts[i] > 0) {
+            Some(i) => {
+                let value = std::mem::take(&mut parts[i]);
+                parts[i - 1] += 1;
+                parts[k - 1] = value - 1;
+            }
+            None => self.parts = None,
+        }
+        self.parts.as_deref()
+    }
+}
+
+impl Iterator for Compositions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_slice().map(<[usize]>::to_vec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_subsets() {
+        let subsets: Vec<_> = subsets(&[1, 2, 3]).collect();
+        assert_eq!(subsets.len(), 8);
+        assert_eq!(subsets[0], Vec::<&i32>::new());
+        assert_eq!(subsets[3], vec![&1, &2]);
+        assert_eq!(subsets[7], vec![&1, &2, &3]);
+        assert_eq!(super::subsets::<u8>(&[]).count(), 1);
+    }
+
+    #[test]
+    fn sized_subsets() {
+        let pairs: Vec<_> = subsets_of_size(&['a', 'b', 'c'], 2).collect();
+        assert_eq!(pairs, vec![vec![&'a', &'b'], vec![&'a', &'c'], vec![&'b', &'c']]);
+        assert_eq!(subsets_of_size(&[1, 2, 3, 4, 5], 3).count(), 10);
+        assert_eq!(subsets_of_size(&[1, 2], 0).collect::<Vec<_>>(), vec![Vec::<&i32>::new()]);
+        assert_eq!(subsets_of_size(&[1, 2], 3).count(), 0);
+    }
+
+    #[test]
+    fn subset_sums_by_size() {
+        // The example from 2015-17: 20+5 (twice), 15+10, and 15+5+5.
+        assert_eq!(subset_sum_counts(&[20, 15, 10, 5, 5], 25), vec![0, 0, 3, 1, 0, 0]);
+        assert_eq!(subset_sum_counts(&[3, 4], 0), vec![1, 0, 0]);
+        assert_eq!(subset_sum_counts(&[], 5), vec![0]);
+    }
+
+    #[test]
+    fn even_partitions() {
+        assert!(partitions_evenly(&[1, 2, 3, 4, 5, 7, 8, 9, 10, 11], 3));
+        assert!(partitions_evenly(&[5, 5], 2));
+        // The sum divides evenly, but the 6 doesn't fit into any group of 4.
+        assert!(!partitions_evenly(&[6, 1, 1], 2));
+        assert!(!partitions_evenly(&[1, 2], 2));
+        assert!(partitions_evenly(&[], 0));
+        assert!(!partitions_evenly(&[1], 0));
+    }
+
+    #[test]
+    fn compositions_in_order() {
+        let all: Vec<_> = compositions(2, 2).collect();
+        assert_eq!(all, vec![vec![0, 2], vec![1, 1], vec![2, 0]]);
+        // Stars and bars: C(n + k - 1, k - 1).
+        assert_eq!(compositions(5, 3).count(), 21);
+        assert_eq!(compositions(0, 3).collect::<Vec<_>>(), vec![vec![0, 0, 0]]);
+        assert_eq!(compositions(0, 0).count(), 1);
+        assert_eq!(compositions(3, 0).count(), 0);
+
+        let mut lent = compositions(2, 2);
+        assert_eq!(lent.next_slice(), Some(&[0, 2][..]));
+        assert_eq!(lent.next(), Some(vec![1, 1]));
+        assert_eq!(lent.next_slice(), Some(&[2, 0][..]));
+        assert_eq!(lent.next_slice(), None);
+        assert_eq!(lent.next(), None);
+    }
+}
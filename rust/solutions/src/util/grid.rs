@@ -0,0 +1,323 @@
+//! A dense two-dimensional grid, for all the puzzles that happen on a map of some sort.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+/// Offsets to the orthogonally adjacent cells; left, up, right and down.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+
+/// Offsets to all adjacent cells; the orthogonal ones first, then the diagonal ones.
+const ALL: [(isize, isize); 8] = [(-1, 0), (0, -1), (1, 0), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+/// A rectangular grid of cells, indexed by `(x, y)` positions; `(0, 0)` is the top left corner.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// The cells, row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size, with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Creates a grid of the given size, with every cell set to the result of calling `f` with its
+    /// position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(&mut f).collect();
+        Grid { width, height, cells }
+    }
+
+    /// Parses lines of puzzle input into a grid, with each byte turned into a cell by `f`. Fails
+    /// if `f` does, or if the lines aren't all of the same length.
+    pub fn parse(input: &[&str], mut f: impl FnMut(u8) -> Option<T>) -> Option<Self> {
+        let width = input.first()?.len();
+        let mut cells = Vec::with_capacity(width * input.len());
+
+        for line in input {
+            if line.len() != width {
+                return None;
+            }
+            for &b in line.as_bytes() {
+                cells.push(f(b)?);
+            }
+        }
+
+        Some(Grid { width, height: input.len(), cells })
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether there are no cells at all.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether `(x, y)` is a position inside the grid.
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Gets the cell at `position`, if it is inside the grid.
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    /// Gets the cell at `position` mutably, if it is inside the grid.
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.index_of(position).map(move |i| &mut self.cells[i])
+    }
+
+    /// Moves from `position` by the offset `(dx, dy)`, if that ends up inside the grid.
+    pub fn offset(&self, position: (usize, usize), delta: (isize, isize)) -> Option<(usize, usize)> {
+        offset_within((self.width, self.height), position, delta)
+    }
+
+    /// The positions orthogonally adjacent to `position` that are inside the grid. Doesn't borrow
+    /// the grid, so cells can be modified while iterating.
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let size = (self.width, self.height);
+        ORTHOGONAL.into_iter().filter_map(move |delta| offset_within(size, position, delta))
+    }
+
+    /// The positions orthogonally or diagonally adjacent to `position` that are inside the grid.
+    /// Doesn't borrow the grid, so cells can be modified while iterating.
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let size = (self.width, self.height);
+        ALL.into_iter().filter_map(move |delta| offset_within(size, position, delta))
+    }
+
+    /// All positions in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells, row by row.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// All cells as one slice, row by row; the cell at `(x, y)` is at `y * width + x`.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// All cells mutably, row by row.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// All cells along with their positions, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The cells of row `y`.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// All rows, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a chunk size of 0, which a grid without columns would have.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// All rows mutably, from top to bottom.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_mut(self.width.max(1))
+    }
+
+    /// The cells of column `x`, from top to bottom. Panics if `x` is outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside of a {}x{} grid", x, self.width, self.height);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// All columns, from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Creates a grid of the same size, with each cell turned into a new one by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// Mirrors the grid along the diagonal from the top left to the bottom right, turning rows
+    /// into columns and vice versa.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, self.height - 1 - x)].clone())
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(self.width - 1 - y, x)].clone())
+    }
+
+    /// Converts a position into an index into `cells`, if it's inside the grid.
+    fn index_of(&self, position: (usize, usize)) -> Option<usize> {
+        self.contains(position).then(|| position.1 * self.width + position.0)
+    }
+}
+
+/// Moves from `(x, y)` by the offset `(dx, dy)`, if that ends up inside a grid of the given size.
+fn offset_within(
+    (width, height): (usize, usize),
+    (x, y): (usize, usize),
+    (dx, dy): (isize, isize),
+) -> Option<(usize, usize)> {
+    let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+    (x < width && y < height).then_some((x, y))
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Gets the cell at the given position. Panics if it is outside of the grid; unlike indexing
+    /// into the underlying `Vec` directly, that includes an x coordinate past the end of a row.
+    fn index(&self, position: (usize, usize)) -> &T {
+        match self.index_of(position) {
+            Some(i) => &self.cells[i],
+            None => panic!("{:?} is outside of a {}x{} grid", position, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        match self.index_of(position) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} is outside of a {}x{} grid", position, self.width, self.height),
+        }
+    }
+}
+
+/// Renders the grid with one line per row, and the cells of each row right next to each other.
+/// Grids of things without a sensible `Display` can be [mapped](Grid::map) to `char`s first.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                cell.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x2 grid of the digits 1 to 6.
+    fn sample() -> Grid<u8> {
+        Grid::parse(&["123", "456"], |b| Some(b - b'0')).unwrap()
+    }
+
+    #[test]
+    fn parse_rows() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid[(0, 1)], 4);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+    }
+
+    #[test]
+    fn parse_failures() {
+        assert_eq!(Grid::parse(&["12", "345"], Some), None);
+        assert_eq!(Grid::parse(&["1x"], |b| (b as char).to_digit(10)), None);
+        assert_eq!(Grid::<u8>::parse(&[], Some), None);
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let grid = sample();
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((2, 1), (0, 1)), None);
+        assert_eq!(grid.offset((0, 0), (2, 1)), Some((2, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of a 3x2 grid")]
+    fn index_past_end_of_row() {
+        // In the underlying Vec, this would be the first cell of the second row.
+        let _ = sample()[(3, 0)];
+    }
+
+    #[test]
+    fn neighbours_at_edges() {
+        let grid = sample();
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4((1, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+        assert_eq!(grid.neighbours8((2, 1)).collect::<Vec<_>>(), vec![(1, 1), (2, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn transformations() {
+        let grid = sample();
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_clockwise().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(grid.map(|&c| c * 2).iter().sum::<u8>(), 42);
+    }
+
+    #[test]
+    fn empty_grid() {
+        let grid = Grid::new(0, 0, 0u8);
+        assert!(grid.is_empty());
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn from_fn_positions() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| (x, y));
+        assert!(grid.enumerate().all(|(p, &value)| p == value));
+        assert_eq!(grid.positions().collect::<Vec<_>>(), grid.iter().copied().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,470 @@
+//! Intervals of integers and sets of them, along with their generalization to any number of
+//! dimensions: axis-aligned boxes, and sets of those.
+//!
+//! Everything here is half-open, start-inclusive and end-exclusive, like [`Range`]; but puzzle
+//! input usually gives both ends inclusively, so there's [`Interval::inclusive`] for that.
+
+use std::ops::{Range, RangeInclusive};
+
+/// The integers from `start` (inclusive) to `end` (exclusive). Empty if `end <= start`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    /// Creates the interval from `start` up to, but not including, `end`.
+    pub const fn new(start: i64, end: i64) -> Self {
+        Interval { start, end }
+    }
+
+    /// Creates the interval from `first` up to and including `last`.
+    pub const fn inclusive(first: i64, last: i64) -> Self {
+        Interval { start: first, end: last + 1 }
+    }
+
+    /// The last number in the interval; the inclusive counterpart to `end`.
+    pub const fn last(self) -> i64 {
+        self.end - 1
+    }
+
+    /// The number of integers in the interval.
+    pub fn len(self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    /// Whether the interval contains no integers at all.
+    pub fn is_empty(self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Whether `x` is in the interval.
+    pub fn contains(self, x: i64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    /// The integers that are in both intervals, if there are any.
+    pub fn intersection(self, other: Interval) -> Option<Interval> {
+        let result = Interval { start: self.start.max(other.start), end: self.end.min(other.end) };
+        (!result.is_empty()).then_some(result)
+    }
+
+    /// Whether the interval contains all of `other`.
+    pub fn covers(self, other: Interval) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(range: Range<i64>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl From<RangeInclusive<i64>> for Interval {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Interval::inclusive(*range.start(), *range.end())
+    }
+}
+
+impl From<Interval> for Range<i64> {
+    fn from(interval: Interval) -> Self {
+        interval.start..interval.end
+    }
+}
+
+/// A set of integers, stored as the intervals they form.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntervalSet {
+    /// Sorted, non-empty, and neither overlapping nor touching each other.
+    intervals: Vec<Interval>,
+}
+
+/// Builds a set out of any intervals, merging those that overlap or touch.
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut intervals: Vec<_> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_by_key(|i| i.start);
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+}
+
+impl IntervalSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The intervals making up the set, in order. None of them overlap or touch.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> i64 {
+        self.intervals.iter().map(|i| i.len()).sum()
+    }
+
+    /// Whether the set contains no integers at all.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Whether `x` is in the set.
+    pub fn contains(&self, x: i64) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= x);
+        self.intervals.get(index).is_some_and(|i| i.contains(x))
+    }
+
+    /// Adds all integers in `interval` to the set.
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // Everything overlapping or touching the new interval gets merged into it. Since those
+        // don't touch each other, nothing else can touch the merged interval either.
+        let mut merged = interval;
+        self.intervals.retain(|&i| {
+            let touches = i.start <= merged.end && merged.start <= i.end;
+            if touches {
+                merged = Interval::new(merged.start.min(i.start), merged.end.max(i.end));
+            }
+            !touches
+        });
+
+        let index = self.intervals.partition_point(|i| i.start < merged.start);
+        self.intervals.insert(index, merged);
+    }
+
+    /// Removes all integers in `interval` from the set.
+    pub fn remove(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        self.intervals = std::mem::take(&mut self.intervals)
+            .into_iter()
+            .flat_map(|i| [Interval::new(i.start, i.end.min(interval.start)), Interval::new(i.start.max(interval.end), i.end)])
+            .filter(|i| !i.is_empty())
+            .collect();
+    }
+
+    /// The integers that are in either set.
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.intervals.iter().chain(&other.intervals).copied().collect()
+    }
+
+    /// The integers that are in both sets.
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = Vec::new();
+        let (mut a, mut b) = (self.intervals.iter().peekable(), other.intervals.iter().peekable());
+
+        // Both lists are sorted, so walk through them side by side, always moving past whichever
+        // interval ends first.
+        while let (Some(&&x), Some(&&y)) = (a.peek(), b.peek()) {
+            result.extend(x.intersection(y));
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// The integers that are in this set, but not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = self.clone();
+        for &interval in &other.intervals {
+            result.remove(interval);
+        }
+        result
+    }
+}
+
+/// An axis-aligned box in `N` dimensions; the points whose coordinate along each axis is in the
+/// respective interval.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Cuboid<const N: usize> {
+    pub axes: [Interval; N],
+}
+
+impl<const N: usize> Cuboid<N> {
+    /// Creates a box spanning the given intervals.
+    pub const fn new(axes: [Interval; N]) -> Self {
+        Cuboid { axes }
+    }
+
+    /// The number of points in the box.
+    pub fn volume(&self) -> i64 {
+        self.axes.iter().map(|i| i.len()).product()
+    }
+
+    /// Whether the box contains no points at all.
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(|i| i.is_empty())
+    }
+
+    /// Whether the point `p` is in the box.
+    pub fn contains(&self, p: [i64; N]) -> bool {
+        self.axes.iter().zip(p).all(|(i, x)| i.contains(x))
+    }
+
+    /// Whether the box contains all of `other`.
+    pub fn covers(&self, other: &Cuboid<N>) -> bool {
+        other.is_empty() || self.axes.iter().zip(&other.axes).all(|(a, &b)| a.covers(b))
+    }
+
+    /// The points that are in both boxes, if there are any.
+    pub fn intersection(&self, other: &Cuboid<N>) -> Option<Cuboid<N>> {
+        let mut axes = self.axes;
+        for (axis, &other) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersection(other)?;
+        }
+        Some(Cuboid { axes })
+    }
+
+    /// The points that are in this box, but not in `other`, as at most `2 * N` boxes that don't
+    /// overlap.
+    pub fn difference(&self, other: &Cuboid<N>) -> Vec<Cuboid<N>> {
+        let Some(shared) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        // Along each axis in turn, cut off the slabs on either side of the shared part, and keep
+        // going with what's left in between.
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for d in 0..N {
+            let axis = rest.axes[d];
+            for slab in [Interval::new(axis.start, shared.axes[d].start), Interval::new(shared.axes[d].end, axis.end)] {
+                if !slab.is_empty() {
+                    let mut piece = rest;
+                    piece.axes[d] = slab;
+                    pieces.push(piece);
+                }
+            }
+            rest.axes[d] = shared.axes[d];
+        }
+        pieces
+    }
+}
+
+/// A set of points in `N` dimensions, stored as boxes that don't overlap.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CuboidSet<const N: usize> {
+    cuboids: Vec<Cuboid<N>>,
+}
+
+impl<const N: usize> Default for CuboidSet<N> {
+    fn default() -> Self {
+        CuboidSet { cuboids: Vec::new() }
+    }
+}
+
+impl<const N: usize> FromIterator<Cuboid<N>> for CuboidSet<N> {
+    fn from_iter<I: IntoIterator<Item = Cuboid<N>>>(iter: I) -> Self {
+        let mut set = CuboidSet::new();
+        for cuboid in iter {
+            set.insert(cuboid);
+        }
+        set
+    }
+}
+
+impl<const N: usize> CuboidSet<N> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The boxes making up the set, in no particular order. None of them overlap.
+    pub fn cuboids(&self) -> &[Cuboid<N>] {
+        &self.cuboids
+    }
+
+    /// The number of points in the set.
+    pub fn volume(&self) -> i64 {
+        self.cuboids.iter().map(|c| c.volume()).sum()
+    }
+
+    /// Whether the set contains no points at all.
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+
+    /// Whether the point `p` is in the set.
+    pub fn contains(&self, p: [i64; N]) -> bool {
+        self.cuboids.iter().any(|c| c.contains(p))
+    }
+
+    /// Adds all points in `cuboid` to the set.
+    pub fn insert(&mut self, cuboid: Cuboid<N>) {
+        if cuboid.is_empty() || self.cuboids.iter().any(|c| c.covers(&cuboid)) {
+            return;
+        }
+        self.remove(cuboid);
+        self.cuboids.push(cuboid);
+    }
+
+    /// Removes all points in `cuboid` from the set.
+    pub fn remove(&mut self, cuboid: Cuboid<N>) {
+        self.cuboids = std::mem::take(&mut self.cuboids)
+            .into_iter()
+            .flat_map(|c| c.difference(&cuboid))
+            .collect();
+    }
+
+    /// The points that are in either set.
+    pub fn union(&self, other: &CuboidSet<N>) -> CuboidSet<N> {
+        let mut result = self.clone();
+        for &cuboid in &other.cuboids {
+            result.insert(cuboid);
+        }
+        result
+    }
+
+    /// The points that are in both sets.
+    pub fn intersection(&self, other: &CuboidSet<N>) -> CuboidSet<N> {
+        // Pieces of two sets of non-overlapping boxes can't overlap either.
+        let cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|a| other.cuboids.iter().filter_map(|b| a.intersection(b)))
+            .collect();
+        CuboidSet { cuboids }
+    }
+
+    /// The points that are in this set, but not in `other`.
+    pub fn difference(&self, other: &CuboidSet<N>) -> CuboidSet<N> {
+        let mut result = self.clone();
+        for &cuboid in &other.cuboids {
+            result.remove(cuboid);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A set out of `(start, end)` pairs.
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals.iter().map(|&(start, end)| Interval::new(start, end)).collect()
+    }
+
+    /// The `(start, end)` pairs making up a set.
+    fn pairs(set: &IntervalSet) -> Vec<(i64, i64)> {
+        set.intervals().iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    /// A box out of `(first, last)` pairs, inclusive like in puzzle input.
+    fn cuboid<const N: usize>(axes: [(i64, i64); N]) -> Cuboid<N> {
+        Cuboid::new(axes.map(|(first, last)| Interval::inclusive(first, last)))
+    }
+
+    #[test]
+    fn interval_basics() {
+        let i = Interval::from(3..=5);
+        assert_eq!(i, Interval::new(3, 6));
+        assert_eq!((i.len(), i.last()), (3, 5));
+        assert!(i.contains(5) && !i.contains(6));
+        assert!(Interval::new(4, 4).is_empty());
+        assert_eq!(Interval::new(5, 2).len(), 0);
+        assert_eq!(Range::from(i), 3..6);
+    }
+
+    #[test]
+    fn interval_intersection_and_covers() {
+        let i = Interval::new(0, 10);
+        assert_eq!(i.intersection(Interval::new(5, 15)), Some(Interval::new(5, 10)));
+        // Touching intervals share nothing, since the end is exclusive.
+        assert_eq!(i.intersection(Interval::new(10, 15)), None);
+        assert!(i.covers(Interval::new(0, 10)));
+        assert!(i.covers(Interval::new(20, 20)));
+        assert!(!i.covers(Interval::new(-1, 5)));
+    }
+
+    #[test]
+    fn set_merges_overlapping_and_touching() {
+        let s = set(&[(5, 8), (0, 2), (2, 4), (7, 10), (12, 12)]);
+        assert_eq!(pairs(&s), vec![(0, 4), (5, 10)]);
+        assert_eq!(s.len(), 9);
+        assert!(s.contains(3) && !s.contains(4) && s.contains(9) && !s.contains(10));
+
+        let mut s = s;
+        s.insert(Interval::new(4, 5));
+        assert_eq!(pairs(&s), vec![(0, 10)]);
+    }
+
+    #[test]
+    fn set_remove_at_bounds() {
+        let mut s = set(&[(0, 10)]);
+
+        // Removing touching intervals leaves everything in place.
+        s.remove(Interval::new(-5, 0));
+        s.remove(Interval::new(10, 15));
+        assert_eq!(pairs(&s), vec![(0, 10)]);
+
+        // Removing a contained interval splits the one around it.
+        s.remove(Interval::new(3, 5));
+        assert_eq!(pairs(&s), vec![(0, 3), (5, 10)]);
+
+        // Removing exactly an interval, or more than it, gets rid of it entirely.
+        s.remove(Interval::new(0, 3));
+        s.remove(Interval::new(4, 11));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12), (15, 20)]);
+        assert_eq!(pairs(&a.union(&b)), vec![(0, 20)]);
+        assert_eq!(pairs(&a.intersection(&b)), vec![(3, 5), (10, 12)]);
+        assert_eq!(pairs(&a.difference(&b)), vec![(0, 3), (12, 15)]);
+        assert_eq!(pairs(&b.difference(&a)), vec![(5, 10), (15, 20)]);
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn cuboid_difference() {
+        let outer = cuboid([(0, 2), (0, 2), (0, 2)]);
+
+        // Cutting out the middle leaves pieces on all six sides.
+        let pieces = outer.difference(&cuboid([(1, 1), (1, 1), (1, 1)]));
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<i64>(), 26);
+
+        // A box that only touches, or covers it entirely.
+        assert_eq!(outer.difference(&cuboid([(3, 5), (0, 2), (0, 2)])), vec![outer]);
+        assert!(outer.difference(&cuboid([(-1, 3), (-1, 3), (-1, 3)])).is_empty());
+    }
+
+    #[test]
+    fn cuboid_set_volume() {
+        let mut s: CuboidSet<2> =
+            [cuboid([(0, 9), (0, 9)]), cuboid([(5, 14), (5, 14)])].into_iter().collect();
+        assert_eq!(s.volume(), 175);
+        assert!(s.contains([12, 12]) && !s.contains([12, 2]));
+
+        s.remove(cuboid([(0, 14), (5, 5)]));
+        assert_eq!(s.volume(), 175 - 15);
+
+        let other: CuboidSet<2> = [cuboid([(0, 4), (0, 4)])].into_iter().collect();
+        assert_eq!(s.intersection(&other).volume(), 25);
+        assert_eq!(s.difference(&other).volume(), 160 - 25);
+        assert_eq!(s.union(&other).volume(), 160);
+    }
+}
@@ -0,0 +1,247 @@
+//! Number theory: divisors, primes and modular arithmetic.
+
+use std::ops::Range;
+
+/// The greatest common divisor of `a` and `b`. `gcd(0, 0)` is 0.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`. `lcm(0, x)` is 0.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// Finds `(g, x, y)` such that `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `base` to the power of `exp`, modulo `modulus`, in `O(log exp)` steps.
+pub fn modpow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let mut result = 1;
+    let mut base = base % modulus;
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result = mulmod(result, base, modulus);
+        }
+        base = mulmod(base, base, modulus);
+        exp /= 2;
+    }
+    result
+}
+
+/// The inverse of `a` modulo `modulus`; that is, the `x` in `0..modulus` for which `a * x` is 1
+/// modulo `modulus`. Only exists if `a` and `modulus` are coprime, and `modulus` is positive.
+pub fn modinv(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves a system of congruences `x = residue (mod modulus)`, given as `(residue, modulus)` pairs,
+/// via the Chinese remainder theorem. The moduli don't need to be coprime.
+///
+/// Returns `(x, m)`, where `x` is the smallest non-negative solution, and all solutions are `x`
+/// plus multiples of `m`, the least common multiple of the moduli. `None` if there is no solution,
+/// if any modulus isn't positive, or if `m` doesn't fit into an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for &(residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+        let (residue, modulus) = (residue as i128, modulus as i128);
+
+        // Looking for a t with x + m * t = residue (mod modulus); that is,
+        // m * t = residue - x (mod modulus).
+        let (g, inverse, _) = extended_gcd(i64::try_from(m).ok()?, modulus as i64);
+        let (g, inverse) = (g as i128, inverse as i128);
+        let difference = residue - x;
+        if difference % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let t = (difference / g % step * inverse).rem_euclid(step);
+        x += m * t;
+        m = i64::try_from(m * step).ok()?.into();
+        x = x.rem_euclid(m);
+    }
+    Some((i64::try_from(x).ok()?, i64::try_from(m).ok()?))
+}
+
+/// Whether `n` is prime. Deterministic for all `u64`s.
+pub fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // Miller-Rabin; checking these witnesses is enough to be sure for anything that fits in 64
+    // bits.
+    let (mut d, mut s) = (n - 1, 0);
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+
+    WITNESSES.into_iter().all(|a| {
+        let mut x = modpow(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mulmod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// The sum of all divisors of each number in `range`, including 1 and the number itself. The
+/// entry for 0 is 0.
+pub fn divisor_sums(range: Range<usize>) -> Vec<u64> {
+    divisor_sums_bounded(range, usize::MAX)
+}
+
+/// Like [`divisor_sums`], but each divisor `d` only counts for its first `multiples` multiples;
+/// that is, for the numbers `n` where `n / d <= multiples`.
+///
+/// Looking at a range rather than everything below a limit means a search can go through
+/// increasingly large ranges without redoing the ones it already looked at.
+pub fn divisor_sums_bounded(range: Range<usize>, multiples: usize) -> Vec<u64> {
+    let Range { start, end } = range;
+    let mut sums = vec![0; end.saturating_sub(start)];
+    for d in 1..end {
+        // The multiples of `d` in the range are `k * d` for these `k`; 0 doesn't count.
+        let first = start.div_ceil(d).max(1);
+        let last = ((end - 1) / d).min(multiples);
+        for k in first..=last {
+            sums[k * d - start] += d as u64;
+        }
+    }
+    sums
+}
+
+/// `a * b` modulo `modulus`, without overflowing.
+fn mulmod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn extended_gcd_signs() {
+        for (a, b) in [(240, 46), (-240, 46), (240, -46), (7, 0), (0, -7)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert!(g >= 0);
+            assert_eq!(a * x + b * y, g);
+        }
+        assert_eq!(extended_gcd(240, 46).0, 2);
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(modpow(4, 13, 497), 445);
+        assert_eq!(modpow(5, 0, 7), 1);
+        assert_eq!(modpow(5, 3, 1), 0);
+        assert_eq!(modpow(u64::MAX, 2, u64::MAX - 1), 1);
+
+        assert_eq!(modinv(3, 11), Some(4));
+        assert_eq!(modinv(-3, 11), Some(7));
+        assert_eq!(modinv(4, 8), None);
+        assert_eq!(modinv(3, 0), None);
+        assert_eq!(modinv(3, -11), None);
+    }
+
+    #[test]
+    fn crt_coprime() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 4)]), Some((3, 4)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_not_coprime() {
+        assert_eq!(crt(&[(2, 6), (8, 10)]), Some((8, 30)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn crt_rejects_bad_moduli() {
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[(1, 3), (1, -5)]), None);
+
+        // Each of these fits into an i64, but their product doesn't.
+        let big = [(1, 1_000_000_007), (2, 998_244_353), (3, 1_000_000_009)];
+        assert_eq!(crt(&big), None);
+    }
+
+    #[test]
+    fn primes() {
+        let small: Vec<_> = (0..30).filter(|&n| is_prime(n)).collect();
+        assert_eq!(small, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(is_prime(1_000_000_007));
+        assert!(!is_prime(1_000_000_007 * 998_244_353));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        // A strong pseudoprime to the bases 2 through 11.
+        assert!(!is_prime(2_152_302_898_747));
+    }
+
+    #[test]
+    fn divisor_sums_in_ranges() {
+        assert_eq!(divisor_sums(0..7), vec![0, 1, 3, 4, 7, 6, 12]);
+        assert_eq!(divisor_sums(4..7), vec![7, 6, 12]);
+        assert!(divisor_sums(5..5).is_empty());
+
+        // 12 has the divisors 1, 2, 3, 4, 6 and 12; but 1 and 2 are past their first 5 multiples.
+        assert_eq!(divisor_sums_bounded(12..13, 5), vec![3 + 4 + 6 + 12]);
+    }
+}
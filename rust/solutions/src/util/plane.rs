@@ -0,0 +1,300 @@
+//! A sparse, unbounded two-dimensional plane; the counterpart to [`Grid`](super::grid::Grid) for
+//! puzzles where things can wander off in any direction.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// The smallest and largest coordinates of the points in a plane, in both directions, as
+/// `((min_x, min_y), (max_x, max_y))`. Both corners are inclusive.
+pub type Bounds = ((i32, i32), (i32, i32));
+
+/// A line to reflect a plane across.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Line {
+    /// The vertical line with the given x coordinate.
+    Vertical(i32),
+    /// The horizontal line with the given y coordinate.
+    Horizontal(i32),
+}
+
+impl Line {
+    /// Reflects `p` across the line.
+    pub fn reflect(self, (x, y): (i32, i32)) -> (i32, i32) {
+        match self {
+            Line::Vertical(at) => (2 * at - x, y),
+            Line::Horizontal(at) => (x, 2 * at - y),
+        }
+    }
+
+    /// Whether `p` is on the right of or below the line.
+    pub fn is_past(self, (x, y): (i32, i32)) -> bool {
+        match self {
+            Line::Vertical(at) => x > at,
+            Line::Horizontal(at) => y > at,
+        }
+    }
+}
+
+/// A set of points on an infinite plane, each with a value attached. Planes without values
+/// (`Plane<()>`) simply work as a set.
+///
+/// The bounding box of all points is kept track of along the way, so it's cheap to get.
+#[derive(Clone, Debug)]
+pub struct Plane<T = ()> {
+    points: HashMap<(i32, i32), T>,
+    /// The bounding box, unless it needs to be recalculated since a point on its edge was removed.
+    bounds: Cell<Option<Bounds>>,
+}
+
+impl<T> Default for Plane<T> {
+    fn default() -> Self {
+        Plane { points: HashMap::new(), bounds: Cell::new(None) }
+    }
+}
+
+/// Planes are equal if they have the same points with the same values.
+impl<T: PartialEq> PartialEq for Plane<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.points == other.points
+    }
+}
+
+impl<T: Eq> Eq for Plane<T> {}
+
+impl<T> FromIterator<((i32, i32), T)> for Plane<T> {
+    fn from_iter<I: IntoIterator<Item = ((i32, i32), T)>>(iter: I) -> Self {
+        let mut plane = Plane::new();
+        for (p, value) in iter {
+            plane.insert(p, value);
+        }
+        plane
+    }
+}
+
+impl FromIterator<(i32, i32)> for Plane<()> {
+    fn from_iter<I: IntoIterator<Item = (i32, i32)>>(iter: I) -> Self {
+        iter.into_iter().map(|p| (p, ())).collect()
+    }
+}
+
+impl Plane<()> {
+    /// Adds the point `p`. Returns whether it's new, like [`HashSet::insert`](std::collections::HashSet::insert).
+    pub fn mark(&mut self, p: (i32, i32)) -> bool {
+        self.insert(p, ()).is_none()
+    }
+}
+
+impl<T> Plane<T> {
+    /// Creates an empty plane.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether there are no points at all.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Whether there is a point at `p`.
+    pub fn contains(&self, p: (i32, i32)) -> bool {
+        self.points.contains_key(&p)
+    }
+
+    /// Gets the value at `p`, if there is a point there.
+    pub fn get(&self, p: (i32, i32)) -> Option<&T> {
+        self.points.get(&p)
+    }
+
+    /// Gets the value at `p` mutably, if there is a point there.
+    pub fn get_mut(&mut self, p: (i32, i32)) -> Option<&mut T> {
+        self.points.get_mut(&p)
+    }
+
+    /// Gets the value at `p` mutably, adding a point with the default value there first if there
+    /// is none yet.
+    pub fn get_or_default(&mut self, p: (i32, i32)) -> &mut T
+    where
+        T: Default,
+    {
+        if !self.points.contains_key(&p) {
+            self.insert(p, T::default());
+        }
+        self.points.get_mut(&p).unwrap()
+    }
+
+    /// Adds a point at `p` with the given value, returning the value that was there before.
+    pub fn insert(&mut self, p: (i32, i32), value: T) -> Option<T> {
+        let (x, y) = p;
+        let bounds = match self.bounds.get() {
+            _ if self.points.is_empty() => Some(((x, y), (x, y))),
+            Some(((min_x, min_y), (max_x, max_y))) => {
+                Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
+            }
+            None => None,
+        };
+        self.bounds.set(bounds);
+        self.points.insert(p, value)
+    }
+
+    /// Removes the point at `p`, returning its value.
+    pub fn remove(&mut self, p: (i32, i32)) -> Option<T> {
+        let value = self.points.remove(&p)?;
+
+        // Only points on the edge of the bounding box can shrink it.
+        if let Some(((min_x, min_y), (max_x, max_y))) = self.bounds.get() {
+            if p.0 == min_x || p.0 == max_x || p.1 == min_y || p.1 == max_y {
+                self.bounds.set(None);
+            }
+        }
+
+        Some(value)
+    }
+
+    /// All points, in no particular order.
+    pub fn points(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.points.keys().copied()
+    }
+
+    /// All points along with their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ((i32, i32), &T)> {
+        self.points.iter().map(|(&p, value)| (p, value))
+    }
+
+    /// The bounding box of all points, or `None` if there are none.
+    pub fn bounds(&self) -> Option<Bounds> {
+        if self.bounds.get().is_none() {
+            let mut points = self.points.keys();
+            let &first = points.next()?;
+            let bounds = points.fold((first, first), |((min_x, min_y), (max_x, max_y)), &(x, y)| {
+                ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+            });
+            self.bounds.set(Some(bounds));
+        }
+        self.bounds.get()
+    }
+
+    /// Moves all points by `(dx, dy)`.
+    pub fn translate(&mut self, (dx, dy): (i32, i32)) {
+        self.map_points(|(x, y)| (x + dx, y + dy));
+    }
+
+    /// Reflects all points across `line`.
+    pub fn reflect(&mut self, line: Line) {
+        self.map_points(|p| line.reflect(p));
+    }
+
+    /// Folds the plane along `line`, reflecting all points past it onto the other side. Where a
+    /// point lands on an existing one, the existing value is kept.
+    pub fn fold(&mut self, line: Line) {
+        let moved: Vec<_> = self.points().filter(|&p| line.is_past(p)).collect();
+        for p in moved {
+            let value = self.remove(p).unwrap();
+            let target = line.reflect(p);
+            if !self.contains(target) {
+                self.insert(target, value);
+            }
+        }
+    }
+
+    /// Renders the bounding box of the plane with one line per row, turning each position into a
+    /// character with `f`, which gets the value there if there is a point.
+    pub fn render(&self, mut f: impl FnMut(Option<&T>) -> char) -> String {
+        let ((min_x, min_y), (max_x, max_y)) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        let mut output = String::new();
+        for y in min_y..=max_y {
+            if y > min_y {
+                output.push('\n');
+            }
+            for x in min_x..=max_x {
+                output.push(f(self.get((x, y))));
+            }
+        }
+        output
+    }
+
+    /// Moves every point to the result of calling `f` with it.
+    fn map_points(&mut self, mut f: impl FnMut((i32, i32)) -> (i32, i32)) {
+        let points = std::mem::take(&mut self.points);
+        self.bounds.set(None);
+        *self = points.into_iter().map(|(p, value)| (f(p), value)).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mark_reports_new_points() {
+        let mut plane = Plane::new();
+        assert!(plane.mark((1, 2)));
+        assert!(!plane.mark((1, 2)));
+        assert!(plane.mark((-3, 0)));
+        assert_eq!(plane.len(), 2);
+    }
+
+    #[test]
+    fn bounds_follow_inserts_and_removes() {
+        let mut plane: Plane = [(0, 0), (5, -2), (-1, 3)].into_iter().collect();
+        assert_eq!(plane.bounds(), Some(((-1, -2), (5, 3))));
+
+        // Removing a point inside the box keeps it; removing one on the edge shrinks it.
+        plane.insert((2, 1), ());
+        plane.remove((2, 1));
+        assert_eq!(plane.bounds(), Some(((-1, -2), (5, 3))));
+        plane.remove((5, -2));
+        assert_eq!(plane.bounds(), Some(((-1, 0), (0, 3))));
+
+        plane.remove((0, 0));
+        plane.remove((-1, 3));
+        assert_eq!(plane.bounds(), None);
+        plane.insert((7, 7), ());
+        assert_eq!(plane.bounds(), Some(((7, 7), (7, 7))));
+    }
+
+    #[test]
+    fn get_or_default_inserts() {
+        let mut plane: Plane<u32> = Plane::new();
+        *plane.get_or_default((1, 1)) += 3;
+        *plane.get_or_default((1, 1)) += 4;
+        assert_eq!(plane.get((1, 1)), Some(&7));
+        assert_eq!(plane.get((0, 0)), None);
+    }
+
+    #[test]
+    fn translate_and_reflect() {
+        let mut plane: Plane = [(0, 0), (2, 1)].into_iter().collect();
+        plane.translate((-1, 1));
+        assert_eq!(plane, [(-1, 1), (1, 2)].into_iter().collect());
+        plane.reflect(Line::Vertical(0));
+        assert_eq!(plane, [(1, 1), (-1, 2)].into_iter().collect());
+        assert_eq!(plane.bounds(), Some(((-1, 1), (1, 2))));
+    }
+
+    #[test]
+    fn fold_keeps_existing_values() {
+        let mut plane: Plane<char> =
+            [((0, 0), 'a'), ((0, 4), 'b'), ((1, 3), 'c'), ((1, 1), 'd')].into_iter().collect();
+        plane.fold(Line::Horizontal(2));
+        assert_eq!(plane.len(), 2);
+        assert_eq!(plane.get((0, 0)), Some(&'a'));
+        assert_eq!(plane.get((1, 1)), Some(&'d'));
+        assert_eq!(plane.bounds(), Some(((0, 0), (1, 1))));
+    }
+
+    #[test]
+    fn render_bounding_box() {
+        let plane: Plane = [(-1, -1), (1, 0)].into_iter().collect();
+        assert_eq!(plane.render(|p| if p.is_some() { '#' } else { '.' }), "#..\n..#");
+        assert_eq!(Plane::<()>::new().render(|_| '#'), "");
+    }
+}
@@ -0,0 +1,352 @@
+//! Points (or vectors; there's no difference here) on integer coordinates in two and three
+//! dimensions, along with the rotations that keep them on integer coordinates.
+
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A point in two dimensions. Like everywhere else in the puzzles, y points down.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A point in three dimensions.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point2 {
+    /// `(0, 0)`.
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    /// Creates a point from its coordinates.
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point2 { x, y }
+    }
+
+    /// The distance to `other` when only moving along the axes.
+    pub fn manhattan(self, other: Point2) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The distance to `other` when diagonal moves are allowed too; that is, the larger of the
+    /// distances along each axis.
+    pub fn chebyshev(self, other: Point2) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Rotates the point around the origin.
+    pub fn rotate(self, rotation: Rotation2) -> Point2 {
+        rotation.apply(self)
+    }
+}
+
+impl Point3 {
+    /// `(0, 0, 0)`.
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    /// Creates a point from its coordinates.
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// The distance to `other` when only moving along the axes.
+    pub fn manhattan(self, other: Point3) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// The distance to `other` when diagonal moves are allowed too; that is, the largest of the
+    /// distances along each axis.
+    pub fn chebyshev(self, other: Point3) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs()).max((self.z - other.z).abs())
+    }
+
+    /// Rotates the point around the origin.
+    pub fn rotate(self, rotation: Rotation3) -> Point3 {
+        rotation.apply(self)
+    }
+
+    /// The coordinates as an array, for when they need to be treated uniformly.
+    fn to_array(self) -> [i32; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+/// Implements the arithmetic operators for a point type, componentwise for points and by scaling
+/// for scalars.
+macro_rules! impl_ops {
+    ($point:ident { $($c:ident),+ }) => {
+        impl Add for $point {
+            type Output = $point;
+            fn add(self, other: $point) -> $point {
+                $point { $($c: self.$c + other.$c),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+            fn sub(self, other: $point) -> $point {
+                $point { $($c: self.$c - other.$c),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+            fn neg(self) -> $point {
+                $point { $($c: -self.$c),+ }
+            }
+        }
+
+        impl Mul<i32> for $point {
+            type Output = $point;
+            fn mul(self, factor: i32) -> $point {
+                $point { $($c: self.$c * factor),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+
+        impl MulAssign<i32> for $point {
+            fn mul_assign(&mut self, factor: i32) {
+                *self = *self * factor;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl From<(i32, i32)> for Point2 {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl From<Point2> for (i32, i32) {
+    fn from(p: Point2) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl From<(i32, i32, i32)> for Point3 {
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl From<Point3> for (i32, i32, i32) {
+    fn from(p: Point3) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+/// One of the 4 rotations in two dimensions that keep points on integer coordinates. They form a
+/// group: any two combined with [`then`](Rotation2::then) are another one, and each can be undone
+/// with its [`inverse`](Rotation2::inverse).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Rotation2 {
+    /// The number of quarter turns to the right; always below 4.
+    quarter_turns: u8,
+}
+
+impl Rotation2 {
+    /// No rotation at all.
+    pub const IDENTITY: Rotation2 = Rotation2 { quarter_turns: 0 };
+    /// A quarter turn clockwise, so that up turns into right.
+    pub const RIGHT: Rotation2 = Rotation2 { quarter_turns: 1 };
+    /// A half turn.
+    pub const HALF: Rotation2 = Rotation2 { quarter_turns: 2 };
+    /// A quarter turn counterclockwise, so that up turns into left.
+    pub const LEFT: Rotation2 = Rotation2 { quarter_turns: 3 };
+    /// All rotations, in order of increasing clockwise turns.
+    pub const ALL: [Rotation2; 4] = [Self::IDENTITY, Self::RIGHT, Self::HALF, Self::LEFT];
+
+    /// Rotates `p` around the origin.
+    pub fn apply(self, p: Point2) -> Point2 {
+        match self.quarter_turns {
+            0 => p,
+            1 => Point2::new(-p.y, p.x),
+            2 => Point2::new(-p.x, -p.y),
+            _ => Point2::new(p.y, -p.x),
+        }
+    }
+
+    /// The rotation that has the effect of doing this one first, and `next` afterwards.
+    pub fn then(self, next: Rotation2) -> Rotation2 {
+        Rotation2 { quarter_turns: (self.quarter_turns + next.quarter_turns) % 4 }
+    }
+
+    /// The rotation that undoes this one.
+    pub fn inverse(self) -> Rotation2 {
+        Rotation2 { quarter_turns: (4 - self.quarter_turns) % 4 }
+    }
+}
+
+/// One of the 24 rotations in three dimensions that keep points on integer coordinates; that is,
+/// the ways of pointing the x axis along any of the 6 directions, and then turning around it in
+/// any of 4 ways. Like [`Rotation2`], they form a group.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Rotation3 {
+    /// The rotation matrix. Each row and column contains exactly one non-zero entry, which is
+    /// either 1 or -1.
+    matrix: [[i32; 3]; 3],
+}
+
+impl Rotation3 {
+    /// No rotation at all.
+    pub const IDENTITY: Rotation3 = Rotation3 { matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]] };
+    /// A quarter turn around the x axis, turning y into z.
+    pub const X: Rotation3 = Rotation3 { matrix: [[1, 0, 0], [0, 0, -1], [0, 1, 0]] };
+    /// A quarter turn around the y axis, turning z into x.
+    pub const Y: Rotation3 = Rotation3 { matrix: [[0, 0, 1], [0, 1, 0], [-1, 0, 0]] };
+    /// A quarter turn around the z axis, turning x into y.
+    pub const Z: Rotation3 = Rotation3 { matrix: [[0, -1, 0], [1, 0, 0], [0, 0, 1]] };
+
+    /// All 24 rotations, starting with the identity.
+    pub fn all() -> impl Iterator<Item = Rotation3> {
+        // Every way of permuting the axes and flipping some of them is either a rotation, or a
+        // rotation combined with a reflection; the latter are the ones with a determinant of -1.
+        const PERMUTATIONS: [([usize; 3], i32); 6] = [
+            ([0, 1, 2], 1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([0, 2, 1], -1),
+            ([2, 1, 0], -1),
+            ([1, 0, 2], -1),
+        ];
+
+        PERMUTATIONS.into_iter().flat_map(|(permutation, parity)| {
+            (0..8).filter_map(move |flips: u8| {
+                let signs = [0, 1, 2].map(|i| if flips & (1 << i) == 0 { 1 } else { -1 });
+                (parity * signs.iter().product::<i32>() == 1).then(|| {
+                    let mut matrix = [[0; 3]; 3];
+                    for (row, cells) in matrix.iter_mut().enumerate() {
+                        cells[permutation[row]] = signs[row];
+                    }
+                    Rotation3 { matrix }
+                })
+            })
+        })
+    }
+
+    /// Rotates `p` around the origin.
+    pub fn apply(self, p: Point3) -> Point3 {
+        let p = p.to_array();
+        let [x, y, z] = self.matrix.map(|row| row.iter().zip(p).map(|(a, b)| a * b).sum());
+        Point3 { x, y, z }
+    }
+
+    /// The rotation that has the effect of doing this one first, and `next` afterwards.
+    pub fn then(self, next: Rotation3) -> Rotation3 {
+        let mut matrix = [[0; 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| next.matrix[i][k] * self.matrix[k][j]).sum();
+            }
+        }
+        Rotation3 { matrix }
+    }
+
+    /// The rotation that undoes this one.
+    pub fn inverse(self) -> Rotation3 {
+        // Rotation matrices are orthogonal, so the inverse is just the transpose.
+        let m = self.matrix;
+        Rotation3 { matrix: [0, 1, 2].map(|i| [m[0][i], m[1][i], m[2][i]]) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point2::new(1, 2) + Point2::new(3, -5);
+        assert_eq!(p, Point2::new(4, -3));
+        p -= Point2::new(1, 1);
+        p *= 2;
+        assert_eq!(p, Point2::new(6, -8));
+        assert_eq!(-p, Point2::new(-6, 8));
+        assert_eq!(Point3::new(1, 2, 3) * -1, -Point3::new(1, 2, 3));
+        assert_eq!(<(i32, i32, i32)>::from(Point3::from((1, 2, 3))), (1, 2, 3));
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Point2::new(3, -4).manhattan(Point2::ORIGIN), 7);
+        assert_eq!(Point2::new(3, -4).chebyshev(Point2::ORIGIN), 4);
+        assert_eq!(Point3::new(1, -2, 3).manhattan(Point3::new(-1, 2, 3)), 6);
+        assert_eq!(Point3::new(1, -2, 3).chebyshev(Point3::new(-1, 2, 3)), 4);
+    }
+
+    #[test]
+    fn rotation2_directions() {
+        let up = Point2::new(0, -1);
+        assert_eq!(up.rotate(Rotation2::RIGHT), Point2::new(1, 0));
+        assert_eq!(up.rotate(Rotation2::LEFT), Point2::new(-1, 0));
+        assert_eq!(up.rotate(Rotation2::HALF), Point2::new(0, 1));
+        assert_eq!(Rotation2::RIGHT.then(Rotation2::RIGHT), Rotation2::HALF);
+        assert_eq!(Rotation2::LEFT.then(Rotation2::HALF), Rotation2::RIGHT);
+    }
+
+    #[test]
+    fn rotation2_inverses() {
+        let p = Point2::new(2, 5);
+        for rotation in Rotation2::ALL {
+            assert_eq!(rotation.then(rotation.inverse()), Rotation2::IDENTITY);
+            assert_eq!(p.rotate(rotation).rotate(rotation.inverse()), p);
+        }
+    }
+
+    #[test]
+    fn rotation3_axes() {
+        assert_eq!(Point3::new(0, 1, 0).rotate(Rotation3::X), Point3::new(0, 0, 1));
+        assert_eq!(Point3::new(0, 0, 1).rotate(Rotation3::Y), Point3::new(1, 0, 0));
+        assert_eq!(Point3::new(1, 0, 0).rotate(Rotation3::Z), Point3::new(0, 1, 0));
+        // Four quarter turns around the same axis do nothing.
+        let z4 = Rotation3::Z.then(Rotation3::Z).then(Rotation3::Z).then(Rotation3::Z);
+        assert_eq!(z4, Rotation3::IDENTITY);
+    }
+
+    #[test]
+    fn rotation3_composition_order() {
+        // First X (y -> z), then Y (z -> x): y ends up on x.
+        let p = Point3::new(0, 1, 0);
+        let combined = Rotation3::X.then(Rotation3::Y);
+        assert_eq!(p.rotate(combined), p.rotate(Rotation3::X).rotate(Rotation3::Y));
+        assert_eq!(p.rotate(combined), Point3::new(1, 0, 0));
+    }
+
+    #[test]
+    fn rotation3_group() {
+        let all: Vec<_> = Rotation3::all().collect();
+        assert_eq!(all[0], Rotation3::IDENTITY);
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 24);
+
+        // A point without symmetries ends up somewhere different under each rotation.
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(all.iter().map(|&r| p.rotate(r)).collect::<HashSet<_>>().len(), 24);
+
+        for &a in &all {
+            assert_eq!(a.then(a.inverse()), Rotation3::IDENTITY);
+            for &b in &all {
+                assert!(all.contains(&a.then(b)));
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Parsing lines of puzzle input by example, with the [`scan!`](crate::scan) macro:
+//!
+//! ```ignore
+//! let (action, x1, y1, x2, y2) = scan!(
+//!     line,
+//!     "{turn on|turn off|toggle} {},{} through {},{}" => &str, usize, usize, usize, usize
+//! )?;
+//! ```
+//!
+//! In the pattern, `{}` captures everything up to the text following it (or the end of the line),
+//! and `{a|b|c}` captures whichever of the given alternatives is there, trying them in order.
+//! Everything else has to match literally; `{{` and `}}` stand for a literal `{` and `}`. Each
+//! capture is then converted into the type given for it, in order; see [`Field`].
+
+/// Parses `line` according to a pattern, and converts the captured fields into the given types,
+/// returning them as a tuple. Fails with a descriptive error if the line doesn't fit the pattern or
+/// a field can't be converted. See [the module documentation](crate::util::scan).
+///
+/// The pattern is checked at compile time, including that there's a type for each field. It is
+/// then only split up into its pieces the first time each `scan!` runs, not for every line.
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $pattern:literal => $($t:ty),+ $(,)?) => {
+        (|| -> anyhow::Result<_> {
+            const _: () = assert!(
+                $crate::util::scan::field_count($pattern) == [$(stringify!($t)),+].len(),
+                "the number of fields in the pattern doesn't match the number of types given",
+            );
+            static PATTERN: std::sync::OnceLock<$crate::util::scan::Pattern> = std::sync::OnceLock::new();
+
+            let line: &str = $line;
+            let pattern = PATTERN.get_or_init(|| {
+                $crate::util::scan::Pattern::new($pattern).expect("checked by `field_count`")
+            });
+            let mut fields = pattern.fields(line)?.into_iter();
+
+            Ok(($(
+                <$t as $crate::util::scan::Field>::from_field(fields.next().unwrap())
+                    .map_err(|err| err.context(format!("in `{}`", line)))?,
+            )+))
+        })()
+    };
+}
+
+/// A type that a field captured by [`scan!`](crate::scan) can be converted into.
+pub trait Field<'a>: Sized {
+    /// Converts the captured text.
+    fn from_field(field: &'a str) -> anyhow::Result<Self>;
+}
+
+impl<'a> Field<'a> for &'a str {
+    fn from_field(field: &'a str) -> anyhow::Result<Self> {
+        Ok(field)
+    }
+}
+
+/// Implements [`Field`] via `FromStr` for the given types.
+macro_rules! impl_field_via_from_str {
+    ($($t:ty),*) => {$(
+        impl<'a> Field<'a> for $t {
+            fn from_field(field: &'a str) -> anyhow::Result<Self> {
+                field.parse().map_err(|_| {
+                    anyhow::anyhow!("failed to parse `{}` as {}", field, stringify!($t))
+                })
+            }
+        }
+    )*};
+}
+
+impl_field_via_from_str!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char, bool, String
+);
+
+/// A piece of a pattern.
+#[derive(Debug)]
+enum Segment {
+    /// Text that has to be there.
+    Literal(String),
+    /// `{}`; captures everything up to the next literal.
+    Any,
+    /// `{a|b|c}`; captures one of the alternatives.
+    OneOf(Vec<String>),
+}
+
+/// A pattern split up into its pieces, ready to match lines against. This is what
+/// [`scan!`](crate::scan) uses under the hood.
+#[derive(Debug)]
+pub struct Pattern {
+    segments: Vec<Segment>,
+}
+
+impl Pattern {
+    /// Splits up a pattern. Fails if it isn't well-formed; see [`field_count`].
+    pub fn new(pattern: &str) -> anyhow::Result<Pattern> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = pattern;
+
+        while let Some(c) = rest.chars().next() {
+            if let Some(after) = rest.strip_prefix("{{").or_else(|| rest.strip_prefix("}}")) {
+                literal.push(c);
+                rest = after;
+            } else if c == '{' {
+                let end = rest
+                    .find('}')
+                    .ok_or(anyhow::anyhow!("unclosed `{{` in pattern `{}`", pattern))?;
+                if matches!(segments.last(), Some(Segment::Any)) && literal.is_empty() {
+                    // There's no telling where one field would end and the next begin.
+                    return Err(anyhow::anyhow!(
+                        "pattern `{}` has a `{{}}` right before another field",
+                        pattern,
+                    ));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(match &rest[1..end] {
+                    "" => Segment::Any,
+                    inner => Segment::OneOf(inner.split('|').map(String::from).collect()),
+                });
+                rest = &rest[end + 1..];
+            } else if c == '}' {
+                return Err(anyhow::anyhow!("unmatched `}}` in pattern `{}`", pattern));
+            } else {
+                literal.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Pattern { segments })
+    }
+
+    /// Matches `line` against the pattern, returning the captured fields in order.
+    pub fn fields<'a>(&self, line: &'a str) -> anyhow::Result<Vec<&'a str>> {
+        let mut rest = line;
+        let mut fields = Vec::new();
+        let mismatch = |rest: &str, expected: String| {
+            anyhow::anyhow!(
+                "expected {} at column {} in `{}`",
+                expected,
+                line.len() - rest.len() + 1,
+                line,
+            )
+        };
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Literal(text) => {
+                    rest = rest
+                        .strip_prefix(text.as_str())
+                        .ok_or_else(|| mismatch(rest, format!("`{}`", text)))?;
+                }
+                Segment::Any => {
+                    let end = match self.segments.get(i + 1) {
+                        Some(Segment::Literal(next)) => rest
+                            .find(next.as_str())
+                            .ok_or_else(|| mismatch(rest, format!("`{}` later on", next)))?,
+                        Some(_) => unreachable!("`{{}}` is checked to be followed by a literal"),
+                        None => rest.len(),
+                    };
+                    fields.push(&rest[..end]);
+                    rest = &rest[end..];
+                }
+                Segment::OneOf(alternatives) => {
+                    let found = alternatives
+                        .iter()
+                        .find(|alternative| rest.starts_with(alternative.as_str()))
+                        .ok_or_else(|| mismatch(rest, format!("one of `{}`", alternatives.join("`, `"))))?;
+                    fields.push(&rest[..found.len()]);
+                    rest = &rest[found.len()..];
+                }
+            }
+        }
+
+        if !rest.is_empty() {
+            return Err(mismatch(rest, String::from("the end of the line")));
+        }
+
+        Ok(fields)
+    }
+}
+
+/// Counts the fields in `pattern`. Panics if the pattern isn't well-formed; that is, if it has an
+/// unclosed `{`, a `}` that doesn't close anything, or a `{}` right before another field, since
+/// there's no telling where one would end and the other begin.
+///
+/// This is a `const fn`, so that [`scan!`](crate::scan) can check its pattern at compile time.
+pub const fn field_count(pattern: &str) -> usize {
+    let bytes = pattern.as_bytes();
+    let (mut i, mut count) = (0, 0);
+    // Whether the last thing in the pattern was a `{}`.
+    let mut after_any = false;
+
+    while i < bytes.len() {
+        let escaped = i + 1 < bytes.len() && bytes[i + 1] == bytes[i];
+        match bytes[i] {
+            b'{' | b'}' if escaped => {
+                after_any = false;
+                i += 2;
+            }
+            b'{' => {
+                if after_any {
+                    panic!("a `{{}}` in a pattern can't be right before another field");
+                }
+                let start = i;
+                while i < bytes.len() && bytes[i] != b'}' {
+                    i += 1;
+                }
+                if i == bytes.len() {
+                    panic!("unclosed `{{` in pattern; use `{{{{` for a literal one");
+                }
+                after_any = i == start + 1;
+                count += 1;
+                i += 1;
+            }
+            b'}' => panic!("unmatched `}}` in pattern; use `}}}}` for a literal one"),
+            _ => {
+                after_any = false;
+                i += 1;
+            }
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The message of an error, including its context.
+    fn message<T>(result: anyhow::Result<T>) -> String {
+        format!("{:#}", result.err().expect("expected an error"))
+    }
+
+    #[test]
+    fn scan_fields() {
+        let (action, x, y) =
+            scan!("turn off 3,-4", "{turn on|turn off|toggle} {},{}" => &str, u32, i64).unwrap();
+        assert_eq!((action, x, y), ("turn off", 3, -4));
+
+        let (name, speed) =
+            scan!("Comet can fly 14 km/s.", "{} can fly {} km/s." => String, f64).unwrap();
+        assert_eq!((name.as_str(), speed), ("Comet", 14.0));
+    }
+
+    #[test]
+    fn alternatives_in_order() {
+        // The first alternative that fits wins, even if a later one would fit better.
+        assert_eq!(scan!("ab", "{a|ab}b" => &str).unwrap(), ("a",));
+        assert!(scan!("ab", "{a|ab}" => &str).is_err());
+    }
+
+    #[test]
+    fn escaped_braces() {
+        assert_eq!(field_count("{{}} {} }}{{"), 1);
+        assert_eq!(scan!("{x} = 5", "{{{}}} = {}" => char, u8).unwrap(), ('x', 5));
+        assert_eq!(Pattern::new("{{}}").unwrap().fields("{}").unwrap(), Vec::<&str>::new());
+        assert!(Pattern::new("{{}}").unwrap().fields("{{}}").is_err());
+    }
+
+    #[test]
+    fn literal_mismatch() {
+        let error = message(scan!("Sue 1: cars 2", "Sue {}: {}: {}" => u32, &str, u32));
+        assert_eq!(error, "expected `: ` later on at column 8 in `Sue 1: cars 2`");
+
+        let error = message(scan!("Damage 8", "Damage: {}" => u32));
+        assert_eq!(error, "expected `Damage: ` at column 1 in `Damage 8`");
+    }
+
+    #[test]
+    fn no_alternative_fits() {
+        let error = message(scan!("jmp c", "{jmp|jie} {a|b}" => &str, char));
+        assert_eq!(error, "expected one of `a`, `b` at column 5 in `jmp c`");
+    }
+
+    #[test]
+    fn trailing_text() {
+        let error = message(scan!("inc a, +1", "{inc|dec} {a|b}" => &str, char));
+        assert_eq!(error, "expected the end of the line at column 6 in `inc a, +1`");
+    }
+
+    #[test]
+    fn bad_conversion() {
+        let error = message(scan!("x=abc", "x={}" => i32));
+        assert_eq!(error, "in `x=abc`: failed to parse `abc` as i32");
+    }
+
+    #[test]
+    fn malformed_patterns() {
+        assert!(Pattern::new("{} {").is_err());
+        assert!(Pattern::new("a } b").is_err());
+        assert!(Pattern::new("{}{a|b}").is_err());
+        assert!(Pattern::new("{}{{{}").is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "right before another field")]
+    fn field_count_rejects_adjacent_fields() {
+        field_count("{}{}");
+    }
+
+    #[test]
+    #[should_panic(expected = "unclosed")]
+    fn field_count_rejects_unclosed_fields() {
+        field_count("a {b");
+    }
+}
@@ -0,0 +1,351 @@
+//! Shortest path searches over implicit graphs: the graph is given as a start state and a closure
+//! that produces the neighbours of a state, so it's only explored as far as needed.
+//!
+//! Each search comes in two flavours: one that only finds the cost of the best path to a goal,
+//! and one (`*_path`) that also finds the path itself, from the start to the goal, inclusive.
+//!
+//! States are hashed to keep track of the best known costs. Searches over states that can simply
+//! be numbered, like the cells of a grid, can use the `*_indexed` versions instead, which keep
+//! those costs in a `Vec`; that's a lot faster when most states get visited anyway.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Finds the least number of steps from `start` to a state for which `is_goal` is true, where
+/// `successors` gives the states reachable from a state in one step.
+pub fn bfs<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(start, successors, is_goal, false).map(|(cost, _)| cost)
+}
+
+/// Like [`bfs`], but also returns the path.
+pub fn bfs_path<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(start, successors, is_goal, true)
+}
+
+/// Finds the lowest cost of getting from `start` to a state for which `is_goal` is true, where
+/// `successors` gives the states reachable from a state in one step, along with the cost of that
+/// step.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    best_first(start, HashMap::new(), successors, |_| C::default(), is_goal, false).map(|(cost, _)| cost)
+}
+
+/// Like [`dijkstra`], but also returns the path.
+pub fn dijkstra_path<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    best_first(start, HashMap::new(), successors, |_| C::default(), is_goal, true)
+}
+
+/// Like [`dijkstra`], but guided by `heuristic`, which estimates the remaining cost from a state to
+/// the nearest goal. The result is only guaranteed to be optimal if the heuristic never
+/// overestimates.
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    best_first(start, HashMap::new(), successors, heuristic, is_goal, false).map(|(cost, _)| cost)
+}
+
+/// Like [`astar`], but also returns the path.
+pub fn astar_path<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    best_first(start, HashMap::new(), successors, heuristic, is_goal, true)
+}
+
+/// Like [`dijkstra`], but for states that `index` numbers from 0 up to (but not including)
+/// `size`, each with a different number.
+pub fn dijkstra_indexed<S, C, I>(
+    start: S,
+    size: usize,
+    index: impl Fn(&S) -> usize,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<C>
+where
+    S: Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let best = Indexed { costs: vec![None; size], index };
+    best_first(start, best, successors, |_| C::default(), is_goal, false).map(|(cost, _)| cost)
+}
+
+/// Like [`astar`], but for states that `index` numbers from 0 up to (but not including) `size`,
+/// each with a different number.
+pub fn astar_indexed<S, C, I>(
+    start: S,
+    size: usize,
+    index: impl Fn(&S) -> usize,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<C>
+where
+    S: Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let best = Indexed { costs: vec![None; size], index };
+    best_first(start, best, successors, heuristic, is_goal, false).map(|(cost, _)| cost)
+}
+
+/// A state reached during a search, along with how it was reached.
+struct Node<S, C> {
+    state: S,
+    /// The cost of the best known path to the state.
+    cost: C,
+    /// The index of the node this one was reached from.
+    parent: Option<usize>,
+}
+
+/// The cost of the best known path to each state seen during a search.
+trait Costs<S, C> {
+    fn get(&self, state: &S) -> Option<C>;
+    fn insert(&mut self, state: &S, cost: C);
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Costs<S, C> for HashMap<S, C> {
+    fn get(&self, state: &S) -> Option<C> {
+        HashMap::get(self, state).copied()
+    }
+
+    fn insert(&mut self, state: &S, cost: C) {
+        HashMap::insert(self, state.clone(), cost);
+    }
+}
+
+/// Costs of states that are numbered by `index`, used by the `*_indexed` searches.
+struct Indexed<F, C> {
+    costs: Vec<Option<C>>,
+    index: F,
+}
+
+impl<S, C: Copy, F: Fn(&S) -> usize> Costs<S, C> for Indexed<F, C> {
+    fn get(&self, state: &S) -> Option<C> {
+        self.costs[(self.index)(state)]
+    }
+
+    fn insert(&mut self, state: &S, cost: C) {
+        self.costs[(self.index)(state)] = Some(cost);
+    }
+}
+
+/// Shared implementation of [`bfs`] and [`bfs_path`]. Only reconstructs the path if `with_path` is
+/// set; otherwise the returned path is empty.
+fn breadth_first<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    with_path: bool,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut nodes = vec![Node { state: start.clone(), cost: 0, parent: None }];
+    let mut queue = VecDeque::from([0]);
+    let mut seen = HashSet::from([start]);
+
+    while let Some(index) = queue.pop_front() {
+        if is_goal(&nodes[index].state) {
+            return Some((nodes[index].cost, if with_path { path_to(&nodes, index) } else { vec![] }));
+        }
+
+        let cost = nodes[index].cost + 1;
+        for state in successors(&nodes[index].state) {
+            if seen.insert(state.clone()) {
+                nodes.push(Node { state, cost, parent: Some(index) });
+                queue.push_back(nodes.len() - 1);
+            }
+        }
+    }
+
+    None
+}
+
+/// Shared implementation of [`dijkstra`] and [`astar`] and their `*_path` and `*_indexed`
+/// versions, with the best known costs kept in `best`. Only reconstructs the path if `with_path`
+/// is set; otherwise the returned path is empty.
+fn best_first<S, C, I>(
+    start: S,
+    mut best: impl Costs<S, C>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+    with_path: bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((heuristic(&start), 0)));
+    best.insert(&start, C::default());
+    let mut nodes = vec![Node { state: start, cost: C::default(), parent: None }];
+
+    // Nodes are never updated; instead, a better path to a state adds a new node, and the now
+    // outdated ones are skipped once they come up.
+    while let Some(Reverse((_, index))) = heap.pop() {
+        let cost = nodes[index].cost;
+        if best.get(&nodes[index].state).is_some_and(|best| best < cost) {
+            continue;
+        }
+
+        if is_goal(&nodes[index].state) {
+            return Some((cost, if with_path { path_to(&nodes, index) } else { vec![] }));
+        }
+
+        for (next, step) in successors(&nodes[index].state) {
+            let next_cost = cost + step;
+            if best.get(&next).is_some_and(|best| best <= next_cost) {
+                continue;
+            }
+
+            best.insert(&next, next_cost);
+            heap.push(Reverse((next_cost + heuristic(&next), nodes.len())));
+            nodes.push(Node { state: next, cost: next_cost, parent: Some(index) });
+        }
+    }
+
+    None
+}
+
+/// Follows the parents of the node at `index` back to the start, and returns the states along the
+/// way, starting with the start.
+fn path_to<S: Clone, C>(nodes: &[Node<S, C>], index: usize) -> Vec<S> {
+    let mut path = vec![nodes[index].state.clone()];
+    let mut current = index;
+    while let Some(parent) = nodes[current].parent {
+        path.push(nodes[parent].state.clone());
+        current = parent;
+    }
+
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small weighted graph: 0 -> 1 -> 3 costs 5, 0 -> 2 -> 3 costs 3, and 4 can't be reached.
+    fn weighted(state: &usize) -> Vec<(usize, u32)> {
+        match state {
+            0 => vec![(1, 1), (2, 2)],
+            1 => vec![(3, 4)],
+            2 => vec![(3, 1)],
+            4 => vec![(0, 1)],
+            _ => vec![],
+        }
+    }
+
+    /// Neighbours on a 5x5 grid with a wall along x = 2, except at y = 4.
+    fn walled((x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x - 1, *y), (x + 1, *y), (*x, y - 1), (*x, y + 1)]
+            .into_iter()
+            .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y) && (x != 2 || y == 4))
+            .collect()
+    }
+
+    #[test]
+    fn start_is_goal() {
+        assert_eq!(bfs(0, |_| vec![1], |&s| s == 0), Some(0));
+        assert_eq!(bfs_path(0, |_| vec![1], |&s| s == 0), Some((0, vec![0])));
+        assert_eq!(dijkstra_path(0, weighted, |&s| s == 0), Some((0, vec![0])));
+    }
+
+    #[test]
+    fn no_successors() {
+        assert_eq!(bfs(0, |_| Vec::<i32>::new(), |&s| s == 1), None);
+        assert_eq!(dijkstra(0, |_| Vec::<(i32, u32)>::new(), |&s| s == 1), None);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert_eq!(bfs(0, |&s| weighted(&s).into_iter().map(|(n, _)| n), |&s| s == 4), None);
+        assert_eq!(dijkstra(0, weighted, |&s| s == 4), None);
+        assert_eq!(astar(0, weighted, |_| 0, |&s| s == 4), None);
+        assert_eq!(dijkstra_indexed(0, 5, |&s| s, weighted, |&s| s == 4), None);
+    }
+
+    #[test]
+    fn bfs_around_wall() {
+        let (steps, path) = bfs_path((0, 0), walled, |&p| p == (4, 0)).unwrap();
+        assert_eq!(steps, 12);
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), ((0, 0), (4, 0)));
+        assert!(path.windows(2).all(|w| walled(&w[0]).contains(&w[1])));
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_detour() {
+        assert_eq!(dijkstra(0, weighted, |&s| s == 3), Some(3));
+        assert_eq!(dijkstra_path(0, weighted, |&s| s == 3), Some((3, vec![0, 2, 3])));
+        assert_eq!(dijkstra_indexed(0, 5, |&s| s, weighted, |&s| s == 3), Some(3));
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let successors = |p: &(i32, i32)| walled(p).into_iter().map(|n| (n, 1 + n.1 as u32));
+        let heuristic = |&(x, y): &(i32, i32)| ((4 - x).abs() + y.abs()) as u32;
+        let goal = |&p: &(i32, i32)| p == (4, 0);
+
+        let expected = dijkstra((0, 0), successors, goal);
+        assert!(expected.is_some());
+        assert_eq!(astar((0, 0), successors, heuristic, goal), expected);
+        assert_eq!(astar_path((0, 0), successors, heuristic, goal).map(|(c, _)| c), expected);
+        let index = |&(x, y): &(i32, i32)| (y * 5 + x) as usize;
+        assert_eq!(astar_indexed((0, 0), 25, index, successors, heuristic, goal), expected);
+    }
+}
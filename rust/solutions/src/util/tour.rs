@@ -0,0 +1,156 @@
+//! Finding the best order to visit a bunch of places in; that is, the best Hamiltonian paths and
+//! cycles through a complete graph, given as a matrix of weights.
+//!
+//! All of these use the Held-Karp algorithm, which takes `O(2^n * n^2)` time and `O(2^n * n)`
+//! memory for `n` nodes; so they're fine for up to [`MAX_NODES`], unlike checking all `n!` orders.
+
+/// The most nodes the searches take on. The table of partial paths takes `2^n * n * 9` bytes, so
+/// about 42 MB at this limit; every node past it doubles that and then some.
+pub const MAX_NODES: usize = 18;
+
+/// Marks partial paths in the table that haven't been reached yet. Real totals never get anywhere
+/// near it with puzzle-sized weights.
+const UNREACHED: i64 = i64::MIN;
+
+/// The best way through the graph that was found: its total weight, and the order of the nodes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tour {
+    pub value: i64,
+    pub order: Vec<usize>,
+}
+
+/// Finds the path visiting each node exactly once with the lowest total weight, starting and
+/// ending anywhere. `weights[a][b]` is the weight of going from node `a` to node `b`. `None` if
+/// there are no nodes, or more than [`MAX_NODES`].
+pub fn shortest_path(weights: &[Vec<i64>]) -> Option<Tour> {
+    held_karp(weights, false, |a, b| a < b)
+}
+
+/// Like [`shortest_path`], but finds the highest total weight instead.
+pub fn longest_path(weights: &[Vec<i64>]) -> Option<Tour> {
+    held_karp(weights, false, |a, b| a > b)
+}
+
+/// Finds the cycle visiting each node exactly once and returning to the start with the lowest
+/// total weight, including the way back. The order starts with node 0, and doesn't repeat it at
+/// the end. `weights[a][b]` is the weight of going from node `a` to node `b`. `None` if there are
+/// no nodes, or more than [`MAX_NODES`].
+pub fn shortest_cycle(weights: &[Vec<i64>]) -> Option<Tour> {
+    held_karp(weights, true, |a, b| a < b)
+}
+
+/// Like [`shortest_cycle`], but finds the highest total weight instead.
+pub fn longest_cycle(weights: &[Vec<i64>]) -> Option<Tour> {
+    held_karp(weights, true, |a, b| a > b)
+}
+
+/// Shared implementation of all the searches. `better(a, b)` decides whether the value `a` is
+/// better than `b`.
+fn held_karp(weights: &[Vec<i64>], cycle: bool, better: impl Fn(i64, i64) -> bool) -> Option<Tour> {
+    let n = weights.len();
+    if n == 0 || n > MAX_NODES {
+        return None;
+    }
+
+    // best[visited * n + last] is the value of the best path that visits exactly the nodes in the
+    // bitmask `visited`, and ends at `last`; and from[visited * n + last] is the node before that.
+    // Cycles can start anywhere, so those always start at node 0. Both are kept as small as
+    // possible, since they're what limits the number of nodes.
+    let full = (1usize << n) - 1;
+    let mut best = vec![UNREACHED; (full + 1) * n];
+    let mut from = vec![0u8; (full + 1) * n];
+    for start in 0..if cycle { 1 } else { n } {
+        best[(1 << start) * n + start] = 0;
+    }
+
+    for visited in 1..=full {
+        for last in 0..n {
+            let value = best[visited * n + last];
+            if value == UNREACHED {
+                continue;
+            }
+            for next in (0..n).filter(|next| visited & (1 << next) == 0) {
+                let index = (visited | (1 << next)) * n + next;
+                let next_value = value + weights[last][next];
+                if best[index] == UNREACHED || better(next_value, best[index]) {
+                    best[index] = next_value;
+                    from[index] = last as u8;
+                }
+            }
+        }
+    }
+
+    let (value, mut last) = (0..n)
+        .filter_map(|last| {
+            let value = Some(best[full * n + last]).filter(|&value| value != UNREACHED)?;
+            Some((if cycle { value + weights[last][0] } else { value }, last))
+        })
+        .reduce(|a, b| if better(b.0, a.0) { b } else { a })?;
+
+    // Follow the nodes back to the start.
+    let mut order = vec![last];
+    let mut visited = full;
+    while visited != 1 << last {
+        let previous = from[visited * n + last] as usize;
+        visited &= !(1 << last);
+        last = previous;
+        order.push(last);
+    }
+    order.reverse();
+
+    Some(Tour { value, order })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// London, Dublin and Belfast, from the 2015-09 example.
+    fn towns() -> Vec<Vec<i64>> {
+        vec![vec![0, 464, 518], vec![464, 0, 141], vec![518, 141, 0]]
+    }
+
+    #[test]
+    fn paths() {
+        let shortest = shortest_path(&towns()).unwrap();
+        assert_eq!(shortest.value, 605);
+        assert!(shortest.order == [0, 1, 2] || shortest.order == [2, 1, 0]);
+        assert_eq!(longest_path(&towns()).unwrap().value, 982);
+    }
+
+    #[test]
+    fn cycles_start_at_zero() {
+        // Going around one way is cheaper than the other.
+        let weights = vec![
+            vec![0, 1, 10, 10],
+            vec![10, 0, 1, 10],
+            vec![10, 10, 0, 1],
+            vec![1, 10, 10, 0],
+        ];
+        assert_eq!(shortest_cycle(&weights), Some(Tour { value: 4, order: vec![0, 1, 2, 3] }));
+        assert_eq!(longest_cycle(&weights), Some(Tour { value: 40, order: vec![0, 3, 2, 1] }));
+    }
+
+    #[test]
+    fn one_node() {
+        let weights = vec![vec![0]];
+        assert_eq!(shortest_path(&weights), Some(Tour { value: 0, order: vec![0] }));
+        assert_eq!(shortest_cycle(&weights), Some(Tour { value: 0, order: vec![0] }));
+    }
+
+    #[test]
+    fn two_nodes() {
+        let weights = vec![vec![0, 3], vec![5, 0]];
+        assert_eq!(shortest_path(&weights), Some(Tour { value: 3, order: vec![0, 1] }));
+        assert_eq!(longest_path(&weights), Some(Tour { value: 5, order: vec![1, 0] }));
+        assert_eq!(shortest_cycle(&weights), Some(Tour { value: 8, order: vec![0, 1] }));
+    }
+
+    #[test]
+    fn node_limits() {
+        assert_eq!(shortest_path(&[]), None);
+        assert_eq!(longest_cycle(&[]), None);
+        let too_many = vec![vec![1; MAX_NODES + 1]; MAX_NODES + 1];
+        assert_eq!(shortest_path(&too_many), None);
+    }
+}